@@ -0,0 +1,173 @@
+use crate::transliteration::chars::{is_arabic_letter, is_mark, SHADDA, SPACE};
+use crate::{normalization, Quranize};
+
+/// Fold of a fragment of a gapped query, and the folded quran forms it may stand for.
+type FragmentForms = (fn(char) -> Option<char>, Vec<Vec<char>>);
+
+impl Quranize {
+    /// Find ayas containing the fragments of `query` in order, e.g. to find a partially remembered aya.
+    /// Fragments are separated by `...` or `…`, and each of them is either alphabetic text,
+    /// transliterated as in [`Quranize::encode`], or Arabic text, as in [`Quranize::find_rasm`].
+    /// The first fragment is found at the start of a word, while the others may start inside a word.
+    /// When `max_gap` is given, at most `max_gap` whole words are allowed between two fragments.
+    /// The whole Alquran is searched; filter the results with [`crate::Scope::contains`]
+    /// to keep the ayas of some suras or juz.
+    /// Returns a list of tuple:
+    /// - `usize`: aya row / aya offset (`0..6236`)
+    /// - `Vec<(usize, usize)>`: start and end of each fragment in the aya
+    ///
+    /// # Examples
+    /// ```
+    /// let q = quranize::Quranize::new();
+    /// let (i, spans) = &q.find_gapped("qul a'udzu ... nas", None)[0];
+    /// let aya = q.get_quran(*i).unwrap();
+    /// let texts = Vec::from_iter(spans.iter().map(|&(j, k)| &aya[j..k]));
+    /// assert_eq!(texts, ["قُل أَعوذُ", "نّاسِ"]);
+    /// assert_eq!(q.find_gapped("qul a'udzu ... nas", Some(0)), []);
+    /// ```
+    pub fn find_gapped(
+        &self,
+        query: &str,
+        max_gap: Option<usize>,
+    ) -> Vec<(usize, Vec<(usize, usize)>)> {
+        let query = query.replace('…', "...");
+        let mut fragments = query.split("...").map(str::trim).filter(|f| !f.is_empty());
+        let Some(first) = fragments.next() else {
+            return vec![];
+        };
+        let locations = match Self::is_rasm(first) {
+            true => self.find_rasm(first),
+            false => { self.encode(first).into_iter() }
+                .flat_map(|(q, _, _)| {
+                    self.find(&q)
+                        .into_iter()
+                        .map(move |(i, j)| (i, j, j + q.len()))
+                })
+                .collect(),
+        };
+        let forms = Vec::from_iter(fragments.map(|f| self.fragment_forms(f)));
+        let mut results = Vec::from_iter(locations.into_iter().filter_map(|(i, j, k)| {
+            let mut spans = vec![(j, k)];
+            for forms in &forms {
+                let (_, end) = spans[spans.len() - 1];
+                let (start, next_end) = self.find_forms_from(i, end, forms)?;
+                if max_gap.is_some_and(|n| self.words_between(i, end, start) > n) {
+                    return None;
+                }
+                spans.push((start, next_end));
+            }
+            Some((i, spans))
+        }));
+        results.sort();
+        results.dedup_by(|x, y| x.0 == y.0 && x.1[0].0 == y.1[0].0);
+        results
+    }
+
+    pub(super) fn is_rasm(fragment: &str) -> bool {
+        normalization::normalize(fragment)
+            .chars()
+            .all(is_arabic_letter)
+    }
+
+    /// Quran text `fragment` may stand for, folded by the returned fold: as in [`ArabicFolding::default`]
+    /// for Arabic text, or by ignoring shadda for the quran forms of alphabetic text.
+    fn fragment_forms(&self, fragment: &str) -> FragmentForms {
+        let fold: fn(char) -> Option<char> = match Self::is_rasm(fragment) {
+            true => normalization::fold_arabic,
+            false => |c| (c != SHADDA).then_some(c),
+        };
+        let mut forms: Vec<Vec<char>> = match Self::is_rasm(fragment) {
+            true => vec![fragment.chars().filter_map(fold).collect()],
+            false => { self.encode(fragment).into_iter() }
+                .map(|(q, _, _)| q.chars().filter_map(fold).collect())
+                .collect(),
+        };
+        forms.retain(|f| !f.is_empty());
+        forms.sort();
+        forms.dedup();
+        (fold, forms)
+    }
+
+    /// Find the first of `forms` in aya `i` from byte offset `from`, returning its start and end,
+    /// including the marks of its last letter.
+    fn find_forms_from(
+        &self,
+        i: usize,
+        from: usize,
+        (fold, forms): &FragmentForms,
+    ) -> Option<(usize, usize)> {
+        let aya = self.saqs[i].2;
+        let chars = Vec::from_iter(
+            { aya[from..].char_indices() }.filter_map(|(j, c)| fold(c).map(|c| (from + j, c))),
+        );
+        (0..chars.len()).find_map(|x| {
+            let form = forms.iter().find(|f| {
+                let cs = chars[x..].iter().map(|&(_, c)| c);
+                f.len() <= chars.len() - x && cs.zip(f.iter()).all(|(c, &y)| c == y)
+            })?;
+            let (j, _) = chars[x + form.len() - 1];
+            let end = j + aya[j..].chars().next()?.len_utf8();
+            let marks = aya[end..].char_indices().find(|&(_, c)| !is_mark(c));
+            Some((chars[x].0, marks.map_or(aya.len(), |(k, _)| end + k)))
+        })
+    }
+
+    /// Count the whole words of aya `i` between byte offsets `start` and `end`.
+    fn words_between(&self, i: usize, start: usize, end: usize) -> usize {
+        let tokens = Vec::from_iter(self.saqs[i].2[start..end].split(SPACE));
+        {
+            tokens
+                .get(1..tokens.len().saturating_sub(1))
+                .unwrap_or_default()
+                .iter()
+        }
+        .filter(|w| w.chars().any(is_arabic_letter))
+        .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_find_gapped() {
+        let q = Quranize::new();
+        let texts = |query, max_gap| {
+            let results = q.find_gapped(query, max_gap).into_iter();
+            Vec::from_iter(results.map(|(i, spans)| {
+                let aya = q.get_quran(i).unwrap();
+                (
+                    i,
+                    Vec::from_iter(spans.into_iter().map(|(j, k)| &aya[j..k])),
+                )
+            }))
+        };
+        assert_eq!(texts("", None), []);
+        assert_eq!(texts("...", None), []);
+        assert_eq!(
+            texts("bismillah ... rohim", None),
+            [
+                (0, vec!["بِسمِ اللَّه", "رَّحيمِ"]),
+                (1513, vec!["بِسمِ اللَّه", "رَحيمٌ"]),
+                (3188, vec!["بِسمِ اللَّه", "رَّحيمِ"]),
+            ]
+        );
+        assert_eq!(
+            texts("qul a'udzu … nas", Some(1)),
+            texts("qul a'udzu ... nas", None)
+        );
+        assert_eq!(
+            texts("قل اعوذ ... الناس", None),
+            [(6230, vec!["قُل أَعوذُ", "النّاسِ"])]
+        );
+        let ikhlas = (6221, vec!["قُل", "اللَّهُ", "أَحَدٌ"]);
+        assert!(texts("qul ... allahu ... ahad", Some(1)).contains(&ikhlas));
+        assert!(!texts("qul ... allahu ... ahad", Some(0)).contains(&ikhlas));
+        assert!(
+            texts("innalloha ... ghofururrohim", Some(2)).len()
+                < texts("innalloha ... ghofururrohim", None).len()
+        );
+    }
+}
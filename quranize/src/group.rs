@@ -0,0 +1,129 @@
+use crate::{Index, Quranize, JUZ_STARTS, SURA_STARTS};
+
+/// Grouping of locations in [`Quranize::find_grouped`] and [`Quranize::count_grouped`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Group {
+    Sura,
+    Juz,
+}
+
+impl Quranize {
+    /// Find `s` in Alquran like [`Quranize::find`], grouping the locations by sura or juz.
+    /// Returns a list of tuple, in mushaf order:
+    /// - `u8`: sura number or juz number
+    /// - `Vec<Index>`: locations in the sura or juz
+    ///
+    /// # Examples
+    /// ```
+    /// use quranize::Group;
+    /// let q = quranize::Quranize::new();
+    /// let groups = q.find_grouped("قُل أَعوذُ", Group::Sura);
+    /// assert_eq!(groups, [(113, vec![(6225, 0)]), (114, vec![(6230, 0)])]);
+    /// let groups = q.find_grouped("قُل أَعوذُ", Group::Juz);
+    /// assert_eq!(groups, [(30, vec![(6225, 0), (6230, 0)])]);
+    /// ```
+    pub fn find_grouped(&self, s: &str, group: Group) -> Vec<(u8, Vec<Index>)> {
+        let key = |i| match group {
+            Group::Sura => self.get_sura(i),
+            Group::Juz => self.get_juz(i),
+        };
+        let mut groups: Vec<(u8, Vec<Index>)> = vec![];
+        for (i, j) in self.find(s) {
+            let k = key(i).unwrap_or_default();
+            match groups.last_mut() {
+                Some((l, locations)) if *l == k => locations.push((i, j)),
+                _ => groups.push((k, vec![(i, j)])),
+            }
+        }
+        groups
+    }
+
+    /// Count the locations of `s` in Alquran, i.e. the length of [`Quranize::find`] results,
+    /// without collecting them.
+    ///
+    /// # Examples
+    /// ```
+    /// let q = quranize::Quranize::new();
+    /// assert_eq!(q.count("قُل أَعوذُ"), 2);
+    /// assert_eq!(q.count("الرَّحمـٰنِ"), q.find("الرَّحمـٰنِ").len());
+    /// assert_eq!(q.count("qul"), 0);
+    /// ```
+    pub fn count(&self, s: &str) -> usize {
+        self.tree
+            .locate(s, 0)
+            .map_or(0, |v| self.tree.count_data(v))
+    }
+
+    /// Count the locations of `s` in Alquran like [`Quranize::count`], per sura or per juz,
+    /// e.g. to chart them.
+    /// Returns a list of tuple, in mushaf order, of the suras or juz where `s` is found:
+    /// - `u8`: sura number or juz number
+    /// - `usize`: location count in the sura or juz
+    ///
+    /// # Examples
+    /// ```
+    /// use quranize::Group;
+    /// let q = quranize::Quranize::new();
+    /// assert_eq!(q.count_grouped("قُل أَعوذُ", Group::Sura), [(113, 1), (114, 1)]);
+    /// assert_eq!(q.count_grouped("قُل أَعوذُ", Group::Juz), [(30, 2)]);
+    /// ```
+    pub fn count_grouped(&self, s: &str, group: Group) -> Vec<(u8, usize)> {
+        let starts = match group {
+            Group::Sura => &SURA_STARTS[..],
+            Group::Juz => &JUZ_STARTS[..],
+        };
+        let mut counts = vec![0; starts.len()];
+        if let Some(v) = self.tree.locate(s, 0) {
+            self.tree.count_data_by(v, starts, &mut counts);
+        }
+        { counts.into_iter().enumerate() }
+            .filter(|&(_, n)| n > 0)
+            .map(|(k, n)| (k as u8 + 1, n))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_find_grouped() {
+        let q = Quranize::new();
+        let groups = q.find_grouped("الم", Group::Sura);
+        assert_eq!(
+            groups.iter().map(|(_, l)| l.len()).sum::<usize>(),
+            q.find("الم").len()
+        );
+        assert!(groups.windows(2).all(|w| w[0].0 < w[1].0));
+        assert_eq!((groups[0].0, groups[1].0), (1, 2));
+        assert!(groups[1].1.contains(&(7, 0)));
+        let groups = q.find_grouped("الم", Group::Juz);
+        assert!(groups
+            .iter()
+            .all(|(k, l)| l.iter().all(|&(i, _)| q.get_juz(i) == Some(*k))));
+        assert_eq!(q.find_grouped("", Group::Juz), []);
+        assert_eq!(q.get_juz(0), Some(1));
+        assert_eq!(q.get_juz(147), Some(1));
+        assert_eq!(q.get_juz(148), Some(2));
+        assert_eq!(q.get_juz(6235), Some(30));
+        assert_eq!(q.get_juz(6236), None);
+        assert_eq!((q.get_sura(148), q.get_aya(148)), (Some(2), Some(142)));
+        assert_eq!((q.get_sura(5241), q.get_aya(5241)), (Some(67), Some(1)));
+    }
+
+    #[test]
+    fn test_count() {
+        let q = Quranize::new();
+        for s in ["الم", "قُل", "اللَّهِ", "عَمَّ يَتَساءَلونَ", "", "qul"]
+        {
+            assert_eq!(q.count(s), q.find(s).len());
+            for group in [Group::Sura, Group::Juz] {
+                let groups = q.find_grouped(s, group);
+                let counts = Vec::from_iter(groups.into_iter().map(|(k, l)| (k, l.len())));
+                assert_eq!(q.count_grouped(s, group), counts);
+            }
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use crate::{normalization, EncodeOptions, Quranize};
+use std::collections::{HashMap, HashSet};
+
+type LatticeResults<'a> = Vec<(String, usize, Vec<&'a str>, f64)>;
+
+impl Quranize {
+    /// Do transliteration on the weighted hypotheses of `lattice` like [`Quranize::encode_with`],
+    /// searching for all of them at once.
+    /// Each item of `lattice` is a list of weighted alternatives of a segment of text,
+    /// e.g. the n-best list of a speech recognizer, or the alternatives of a word.
+    /// A hypothesis takes one alternative of every segment, separated by spaces,
+    /// and is weighted with the product of their weights.
+    /// The suffix tree is walked once, branching over the alternatives of a segment
+    /// where the alternative of the segment before ends.
+    ///
+    /// Returns the results of [`Quranize::encode`] together with their score,
+    /// i.e. the total weight of the hypotheses they are found with, from the highest score.
+    /// Their explanation is the one of the hypothesis with the highest weight.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let q = quranize::Quranize::new();
+    /// let n_best = vec![("bismillah", 0.5), ("bismi llah", 0.25), ("bisrnillah", 0.25)];
+    /// let results = q.encode_lattice(&[n_best], &Default::default());
+    /// assert_eq!((results[0].0.as_str(), results[0].3), ("بِسمِ اللَّه", 0.75));
+    /// ```
+    pub fn encode_lattice(
+        &self,
+        lattice: &[Vec<(&str, f64)>],
+        options: &EncodeOptions,
+    ) -> LatticeResults<'_> {
+        if lattice.is_empty() {
+            return vec![];
+        }
+        let normalize = Self::normalizer(options);
+        let normalized = |f: fn(&str) -> String| {
+            Vec::from_iter(
+                lattice
+                    .iter()
+                    .map(|alternatives| Vec::from_iter(alternatives.iter().map(|(s, _)| f(s)))),
+            )
+        };
+        let hypothesis = |path: &[usize]| {
+            Vec::from_iter({ lattice.iter().zip(path) }.map(|(alternatives, &k)| alternatives[k]))
+        };
+        let results_iter = { self.encode_paths("", &normalized(normalize), &options.find) }
+            .into_iter()
+            .filter(|(q, _, e, path)| {
+                !options.strict_spaces || {
+                    let text =
+                        Vec::from_iter(hypothesis(path).into_iter().map(|(s, _)| s)).join(" ");
+                    Self::misaligned_breaks(q, e, &Self::word_breaks(&text, normalize)) == 0
+                }
+            });
+        let muqottoah_lattice = normalized(normalization::normalize_muqottoah);
+        let muqottoah_results_iter = self.encode_muqottoah("", &muqottoah_lattice, &options.find);
+        let mut path_results = Vec::from_iter(
+            { results_iter.chain(muqottoah_results_iter) }
+                .filter(|(q, ..)| !q.is_empty())
+                .map(|(q, found, e, path)| {
+                    let x = hypothesis(&path)
+                        .into_iter()
+                        .map(|(_, x)| x)
+                        .product::<f64>();
+                    ((q, Self::count_found(&found), e), x, path)
+                }),
+        );
+        path_results.sort_by(|(_, x, _), (_, y, _)| y.total_cmp(x));
+        let mut results: LatticeResults = vec![];
+        let mut positions: HashMap<String, usize> = HashMap::new();
+        let mut paths = HashSet::new();
+        for ((q, n, e), x, path) in path_results {
+            if !paths.insert((q.clone(), path)) {
+                continue;
+            }
+            match positions.get(&q) {
+                Some(&k) => results[k].3 += x,
+                None => {
+                    positions.insert(q.clone(), results.len());
+                    results.push((q, n, e, x));
+                }
+            }
+        }
+        results.sort_by(|x, y| y.3.total_cmp(&x.3));
+        results
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_encode_lattice() {
+        let q = Quranize::new();
+        let options = EncodeOptions::default();
+        assert_eq!(q.encode_lattice(&[], &options), []);
+        let lattice = [
+            vec![("qul", 0.75), ("kul", 0.25)],
+            vec![("huwallahu", 0.5), ("huwa allahu", 0.5)],
+        ];
+        let results = q.encode_lattice(&lattice, &options);
+        assert_eq!(results[0].0, "قُل هُوَ اللَّهُ");
+        assert_eq!(results[0].3, 1.0);
+        let (quran, count, explanations) = &q.encode("qul huwallahu")[0];
+        assert_eq!(
+            (&results[0].0, results[0].1, &results[0].2),
+            (quran, *count, explanations)
+        );
+        assert!(results.windows(2).all(|w| w[0].3 >= w[1].3));
+        let n_best = [vec![("alif lam mim", 0.5), ("alif lam", 0.5)]];
+        let results = q.encode_lattice(&n_best, &options);
+        assert_eq!(
+            results[0],
+            ("الم".to_string(), 912, vec!["alif", "lam", "mim"], 0.5)
+        );
+        let split = [vec![("alif", 1.0)], vec![("lam mim", 1.0)]];
+        assert_eq!(q.encode_lattice(&split, &options)[0].0, "الم");
+        let syllables = ["bis", "mil", "la", "hir", "roh", "ma", "nir", "ro", "him"];
+        let lattice = Vec::from_iter(
+            { syllables.into_iter() }.map(|s| vec![(s, 0.8), ("xyz", 0.1), ("", 0.1)]),
+        );
+        let results = q.encode_lattice(&lattice, &options);
+        assert_eq!(results[0].0, q.encode("bismillahirrohmanirrohim")[0].0);
+        assert!((results[0].3 - 0.8f64.powi(9)).abs() < 1e-9);
+    }
+}
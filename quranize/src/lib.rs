@@ -24,15 +24,40 @@
 //! assert_eq!((sura, aya), (78, 1));
 //! ```
 
+use std::collections::HashMap;
+
+mod gapped;
+mod group;
+mod lattice;
+mod matches;
 mod normalization;
+mod pattern;
+mod query;
+mod scope;
+pub mod stats;
 mod suffix_tree;
+pub mod tajweed;
 mod transliteration;
 
-use suffix_tree::{Edge, Index};
-use transliteration::{contextual_map, harf_muqottoah_map, map};
+pub use group::Group;
+pub use matches::Match;
+pub use normalization::ArabicFolding;
+pub use query::QueryError;
+pub use scope::Scope;
+pub use transliteration::{Condition, ContextualRule, Table, TableError};
+
+use suffix_tree::{is_separator, Edge, Index};
+use transliteration::chars::{
+    is_arabic_letter, is_mark, is_waqf_mark, FATHATAN, LETTER_ALEF, LETTER_WAW, SPACE,
+};
+use transliteration::Context;
 
-type EncodeResults = Vec<(String, usize, Vec<&'static str>)>;
-type PrevMap = (char, &'static str);
+type EncodeResults<'a> = Vec<(String, usize, Vec<&'a str>)>;
+/// Vertices of the suffix tree below which a quran form is found, with their location counts.
+type Found = Vec<(usize, usize)>;
+/// Results of [`Quranize::encode`] with the alternative taken from each segment of a lattice,
+/// where the locations of each quran form are kept as the vertices they are found below.
+type PathResults<'a> = Vec<(String, Found, Vec<&'a str>, Vec<usize>)>;
 
 const AYA_COUNT: usize = 6236;
 const SURA_STARTS: [usize; 114] = [
@@ -45,12 +70,60 @@ const SURA_STARTS: [usize; 114] = [
     6130, 6138, 6146, 6157, 6168, 6176, 6179, 6188, 6193, 6197, 6204, 6207, 6213, 6216, 6221, 6225,
     6230,
 ];
+const JUZ_STARTS: [usize; 30] = [
+    0, 148, 259, 385, 516, 640, 750, 899, 1041, 1200, 1327, 1478, 1648, 1802, 2029, 2214, 2483,
+    2673, 2875, 3214, 3385, 3563, 3732, 4089, 4264, 4510, 4705, 5104, 5241, 5672,
+];
 const QURAN_TXT: &str = include_str!("quran-simple-min.txt");
 
+/// Options of [`Quranize::encode_with`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EncodeOptions {
+    /// Read uppercase `S`, `D`, `T`, `Z` and `H` as the emphatic letters
+    /// ص, ض, ط, ظ and ح only, instead of lowercasing them.
+    pub case_sensitive: bool,
+    /// Only return results having a word break wherever `s` has a space.
+    /// Otherwise such results only come first.
+    pub strict_spaces: bool,
+    /// Read the digits of Arabizi as the letters they stand for, e.g. `7` as ح, `3` as ع,
+    /// `2` as hamza, `5` as خ, `6` as ط and `9` as ص. Otherwise digits are ignored.
+    pub arabizi: bool,
+    /// Where the quran forms must be found, as in [`Quranize::find_with`].
+    pub find: FindOptions,
+}
+
+/// Options of [`Quranize::find_with`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FindOptions {
+    /// Only find whole words, i.e. text ending right before a space, a waqf mark or the end of an aya.
+    /// In [`Quranize::encode_with`], quran forms are completed up to the end of the word
+    /// with the following marks, which are silent when stopping,
+    /// and with the silent alef after waw jama'a or fathatan.
+    pub whole_word: bool,
+    /// Only find text at the start of an aya.
+    pub aya_start: bool,
+    /// Only find text in the ayas of this scope.
+    /// In [`Quranize::encode_with`], location counts only count the locations in this scope.
+    pub scope: Scope,
+}
+
+/// Text of a [`Match`] together with the words around it, as returned by [`Quranize::context`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct MatchContext<'a> {
+    /// Words before the text, as pairs of aya row and text, in reading order.
+    pub before: Vec<(usize, &'a str)>,
+    /// Text of the match, extended to whole words.
+    pub text: &'a str,
+    /// Words after the text, as pairs of aya row and text, in reading order.
+    pub after: Vec<(usize, &'a str)>,
+}
+
 /// Quranize model, for doing transliteration, finding string, and getting aya.
 pub struct Quranize {
     tree: suffix_tree::SuffixTree<'static>,
     saqs: Vec<(u8, u16, &'static str)>,
+    table: Table,
 }
 
 impl Quranize {
@@ -58,6 +131,29 @@ impl Quranize {
 
     /// Create a new [`Quranize`] instance.
     pub fn new() -> Self {
+        Self::with_valid_table(Table::default())
+    }
+
+    /// Create a new [`Quranize`] instance, transliterating with `table` instead of the default one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut table = quranize::Table::default();
+    /// table.harf_muqottoah_map.insert('\u{0646}', vec!["en".to_string()]);
+    /// let q = quranize::Quranize::with_table(table).unwrap();
+    /// assert_eq!(q.encode("en")[0].0, "ن");
+    ///
+    /// let mut table = quranize::Table::default();
+    /// table.map.insert('x', vec!["x".to_string()]);
+    /// assert!(quranize::Quranize::with_table(table).is_err());
+    /// ```
+    pub fn with_table(table: Table) -> Result<Self, TableError> {
+        table.validate()?;
+        Ok(Self::with_valid_table(table))
+    }
+
+    fn with_valid_table(table: Table) -> Self {
         let mut tree = suffix_tree::SuffixTree::with_capacity(Self::EXPECTED_VERTEX_COUNT);
         let mut saqs = Vec::with_capacity(AYA_COUNT);
         let mut sura_num = 1;
@@ -73,7 +169,7 @@ impl Quranize {
                 tree.construct(i, q);
                 saqs.push((s, a, q.trim()));
             });
-        Self { tree, saqs }
+        Self { tree, saqs, table }
     }
 
     fn trim_basmalah(s: u8, a: u16, q: &str) -> &str {
@@ -89,89 +185,417 @@ impl Quranize {
     /// - `usize`: location count where the quran form above is found in Alquran
     /// - `Vec<&'static str>`: explanation for each chars in the quran form above
     ///
+    /// Arabic letters in `s` are matched literally, as in [`Quranize::encode_arabic`],
+    /// so `s` can mix alphabetic and Arabic text.
+    /// Results having word breaks where `s` has spaces come first.
+    ///
     /// # Examples
     ///
     /// ```
     /// let q = quranize::Quranize::new();
     /// assert_eq!(q.encode("alif lam mim"), [("الم".to_string(), 912, vec!["alif", "lam", "mim"])]);
+    /// assert_eq!(q.encode("قل huwallahu ahad")[0].0, "قُل هُوَ اللَّهُ أَحَد");
     /// assert_eq!(q.encode("minal jinnati wannas")[0].0, "مِنَ الجِنَّةِ وَالنّاس");
     /// ```
-    pub fn encode(&self, s: &str) -> EncodeResults {
-        let mut results: EncodeResults = match normalization::normalize(s).as_str() {
+    pub fn encode(&self, s: &str) -> EncodeResults<'_> {
+        self.encode_with(s, &EncodeOptions::default())
+    }
+
+    /// Do transliteration on `s` like [`Quranize::encode`], with `options`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let q = quranize::Quranize::new();
+    /// let options = quranize::EncodeOptions { case_sensitive: true, ..Default::default() };
+    /// assert_eq!(q.encode_with("Sirot", &options)[0].0, "صِراط");
+    /// assert!(q.encode_with("sirot", &options).len() > q.encode_with("Sirot", &options).len());
+    /// ```
+    pub fn encode_with(&self, s: &str, options: &EncodeOptions) -> EncodeResults<'_> {
+        { self.encode_found(s, options).into_iter() }
+            .map(|(q, found, e)| (q, Self::count_found(&found), e))
+            .collect()
+    }
+
+    /// Do transliteration on `s` like [`Quranize::encode_with`],
+    /// keeping the vertices below which each quran form is found instead of counting its locations.
+    fn encode_found(&self, s: &str, options: &EncodeOptions) -> Vec<(String, Found, Vec<&str>)> {
+        let normalize = Self::normalizer(options);
+        let breaks = Self::word_breaks(s, normalize);
+        let results_iter = match normalize(s).as_str() {
             "" => vec![],
-            s => { self.tree.edges_from(0) }
-                .flat_map(|&e| self.rev_encode(s, e, None))
-                .collect(),
+            s => self.encode_paths(s, &[], &options.find),
         }
         .into_iter()
-        .chain(match normalization::normalize_muqottoah(s).as_str() {
+        .map(|(q, found, e, _)| (Self::misaligned_breaks(&q, &e, &breaks), (q, found, e)));
+        let muqottoah_results_iter = { self.encode_muqottoah(s, &[], &options.find) }
+            .into_iter()
+            .map(|(q, found, e, _)| (0, (q, found, e)));
+        let mut scored_results: HashMap<String, (usize, usize, _)> = HashMap::new();
+        let scored_results_iter = results_iter.chain(muqottoah_results_iter).enumerate();
+        for (k, (m, (q, found, e))) in scored_results_iter {
+            if m > 0 && options.strict_spaces {
+                continue;
+            }
+            match scored_results.get(&q) {
+                Some(&(best, _, _)) if best <= m => (),
+                _ => _ = scored_results.insert(q, (m, k, (found, e))),
+            }
+        }
+        let mut scored_results = Vec::from_iter(scored_results);
+        scored_results.sort_by_key(|&(_, (m, k, _))| (m, k));
+        { scored_results.into_iter() }
+            .map(|(q, (_, _, (found, e)))| (q, found, e))
+            .collect()
+    }
+
+    fn count_found(found: &Found) -> usize {
+        found.iter().map(|&(_, n)| n).sum()
+    }
+
+    /// Collect the locations below the vertices of `found` which are allowed by `find`, in mushaf order.
+    fn collect_found(&self, found: &Found, find: &FindOptions) -> Vec<Index> {
+        let mut locations = Vec::from_iter(
+            { found.iter() }.flat_map(|&(v, _)| self.tree.collect_data_in(v, &find.scope)),
+        );
+        locations.retain(|&(_, j)| !find.aya_start || j == 0);
+        locations.sort();
+        locations
+    }
+
+    fn normalizer(options: &EncodeOptions) -> fn(&str) -> String {
+        match (options.case_sensitive, options.arabizi) {
+            (false, false) => normalization::normalize,
+            (false, true) => normalization::normalize_arabizi,
+            (true, false) => normalization::normalize_case_sensitive,
+            (true, true) => normalization::normalize_case_sensitive_arabizi,
+        }
+    }
+
+    /// Do transliteration on `s` into harf muqottoah, followed by the alternatives of `lattice`
+    /// already normalized with [`normalization::normalize_muqottoah`].
+    fn encode_muqottoah(
+        &self,
+        s: &str,
+        lattice: &[Vec<String>],
+        find: &FindOptions,
+    ) -> PathResults<'_> {
+        let s = normalization::normalize_muqottoah(s);
+        match (s.as_str(), lattice) {
+            ("", []) => vec![],
+            (s, lattice) => {
+                self.continue_muqottoah(s, (0, 0, ""), find, lattice)
+                    .into_iter()
+            }
+            .map(|(q, found, e, path)| {
+                let q = q.chars().rev().collect();
+                (
+                    q,
+                    found,
+                    e.into_iter().rev().collect(),
+                    path.into_iter().rev().collect(),
+                )
+            })
+            .collect(),
+        }
+    }
+
+    /// Offsets in normalized `s` where the words of `s` are separated.
+    fn word_breaks(s: &str, normalize: fn(&str) -> String) -> Vec<usize> {
+        let mut offset = 0;
+        let word_lens = s.split_whitespace().map(|w| normalize(w).chars().count());
+        let mut breaks = Vec::from_iter(word_lens.filter(|&n| n > 0).map(|n| {
+            offset += n;
+            offset
+        }));
+        breaks.pop();
+        breaks
+    }
+
+    /// Count the `breaks` not falling on a word break of quran form `q` explained by `e`.
+    fn misaligned_breaks(q: &str, e: &[&str], breaks: &[usize]) -> usize {
+        let mut offset = 0;
+        let mut quran_breaks = Vec::with_capacity(breaks.len());
+        for (c, tsl) in q.chars().zip(e) {
+            if c == SPACE || is_waqf_mark(c) {
+                quran_breaks.push(offset);
+            }
+            offset += tsl.chars().count();
+        }
+        { breaks.iter() }
+            .filter(|b| !quran_breaks.contains(b))
+            .count()
+    }
+
+    /// Do transliteration on Arabic text `s` written without harakat, returning the quran forms
+    /// it matches, in the same format as [`Quranize::encode`].
+    /// Orthographic variants are folded as in [`ArabicFolding::default`],
+    /// and the explanation of each quran char is the letter it matches, or empty for marks and spaces.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let q = quranize::Quranize::new();
+    /// let (quran, count, _) = &q.encode_arabic("قل هو الله احد")[0];
+    /// assert_eq!((quran.as_str(), *count), ("قُل هُوَ اللَّهُ أَحَد", 1));
+    /// ```
+    pub fn encode_arabic(&self, s: &str) -> EncodeResults<'_> {
+        let mut results: EncodeResults = match normalization::normalize_arabic(s).as_str() {
             "" => vec![],
-            s => { self.tree.edges_from(0) }
-                .flat_map(|&e| self.rev_encode_muqottoah(s, e))
-                .collect(),
-        })
-        .map(|(q, n, e)| (q.chars().rev().collect(), n, e.into_iter().rev().collect()))
+            s => self.encode_paths(s, &[], &FindOptions::default()),
+        }
+        .into_iter()
+        .map(|(q, found, e, _)| (q, Self::count_found(&found), e))
         .collect();
         results.dedup_by(|x, y| x.0 == y.0);
         results
     }
 
-    fn rev_encode(&self, s: &str, (v, w, l): Edge, pm: Option<PrevMap>) -> EncodeResults {
-        let results_iter = l.chars().next().into_iter().flat_map(|c| -> EncodeResults {
-            let tsls = map(c).iter().chain(contextual_map(pm.unzip().0, c));
-            let tsl_results_iter = tsls.filter_map(|&tsl| -> Option<EncodeResults> {
-                s.strip_prefix(tsl).map(|s| match s {
-                    "" => vec![(c.to_string(), self.tree.count_data(w), vec![tsl])],
-                    s => match &l[c.len_utf8()..] {
-                        "" => { self.tree.edges_from(w) }
-                            .flat_map(|&e| self.rev_encode(s, e, Some((c, tsl))))
-                            .collect(),
-                        l => self.rev_encode(s, (v, w, l), Some((c, tsl))),
-                    }
-                    .into_iter()
-                    .map(|(mut q, n, mut e)| {
-                        q.push(c);
-                        e.push(tsl);
-                        (q, n, e)
-                    })
-                    .collect(),
+    /// Do transliteration on normalized `s`, followed by the alternatives of `lattice`,
+    /// from the root of the suffix tree, returning the results in reading order.
+    /// Results found below different separators, e.g. a space and the end of an aya
+    /// in whole word mode, are merged, counting each vertex once.
+    fn encode_paths(
+        &self,
+        s: &str,
+        lattice: &[Vec<String>],
+        find: &FindOptions,
+    ) -> PathResults<'_> {
+        let mut results: PathResults = vec![];
+        let mut positions: HashMap<_, usize> = HashMap::new();
+        for (q, found, e, path) in
+            self.continue_encode(s, (0, 0, ""), &Context::default(), false, find, lattice)
+        {
+            let q: String = q.chars().rev().collect();
+            let e = Vec::from_iter(e.into_iter().rev());
+            let path = Vec::from_iter(path.into_iter().rev());
+            let key = (q.clone(), e.clone(), path.clone());
+            match positions.get(&key) {
+                Some(&k) => results[k].1.extend(found),
+                None => {
+                    positions.insert(key, results.len());
+                    results.push((q, found, e, path));
+                }
+            }
+        }
+        for (_, found, _, _) in &mut results {
+            found.sort_unstable();
+            found.dedup();
+        }
+        results
+    }
+
+    /// Continue transliteration on the rest of `s` from `(v, w, l)`,
+    /// branching over the alternatives of the next segment of `lattice` when `s` is consumed.
+    fn continue_encode<'a>(
+        &'a self,
+        s: &str,
+        (v, w, l): Edge<'a>,
+        ctx: &Context<'a>,
+        literal: bool,
+        find: &FindOptions,
+        lattice: &[Vec<String>],
+    ) -> PathResults<'a> {
+        match (s, lattice.split_first()) {
+            ("", Some((alternatives, lattice))) => { alternatives.iter().enumerate() }
+                .flat_map(|(k, s)| {
+                    { self.continue_encode(s, (v, w, l), ctx, literal, find, lattice) }
+                        .into_iter()
+                        .map(move |(q, found, e, mut path)| {
+                            path.push(k);
+                            (q, found, e, path)
+                        })
                 })
-            });
-            tsl_results_iter.flatten().collect()
-        });
-        results_iter.collect()
-    }
-
-    fn rev_encode_muqottoah(&self, s: &str, (v, w, l): Edge) -> EncodeResults {
-        let results_iter = l.chars().next().into_iter().flat_map(|c| -> EncodeResults {
-            let tsls = harf_muqottoah_map(c).iter();
-            let tsl_results_iter = tsls.filter_map(|&tsl| -> Option<EncodeResults> {
-                s.strip_prefix(tsl).map(|s| match s {
-                    "" => match self.tree.vertices[w].2 {
-                        true => vec![(c.to_string(), self.tree.count_data(w), vec![tsl])],
-                        false => vec![],
-                    },
-                    s => match &l[c.len_utf8()..] {
-                        "" => { self.tree.edges_from(w) }
-                            .flat_map(|&e| self.rev_encode_muqottoah(s, e))
-                            .collect(),
-                        l => self.rev_encode_muqottoah(s, (v, w, l)),
-                    }
-                    .into_iter()
-                    .map(|(mut q, n, mut e)| {
-                        q.push(c);
-                        e.push(tsl);
-                        (q, n, e)
-                    })
+                .collect(),
+            ("", None) if !find.whole_word => match self.find_following((v, w, l), ctx, find) {
+                found if found.is_empty() => vec![],
+                found => vec![(String::new(), found, vec![], vec![])],
+            },
+            _ => match l {
+                "" => { self.tree.edges_from(w) }
+                    .flat_map(|&e| self.rev_encode(s, e, ctx, literal, find, lattice))
                     .collect(),
+                _ => self.rev_encode(s, (v, w, l), ctx, literal, find, lattice),
+            },
+        }
+    }
+
+    /// `literal` tells whether the quran char before `l` matched an Arabic letter in `s`,
+    /// in which case the marks on that letter are optional.
+    /// When `s` is consumed in whole word mode, `l` is followed up to the next word break.
+    fn rev_encode<'a>(
+        &'a self,
+        s: &str,
+        (v, w, l): Edge<'a>,
+        ctx: &Context<'a>,
+        literal: bool,
+        find: &FindOptions,
+        lattice: &[Vec<String>],
+    ) -> PathResults<'a> {
+        let Some(c) = l
+            .chars()
+            .next()
+            .filter(|_| self.tree.overlaps(w, &find.scope))
+        else {
+            return vec![];
+        };
+        if s.is_empty() && is_separator(c) {
+            return match self.find_following((v, w, l), ctx, find) {
+                found if found.is_empty() => vec![],
+                found => vec![(String::new(), found, vec![], vec![])],
+            };
+        }
+        if s.is_empty() && !Self::is_silent_at_word_end(c, ctx) {
+            return vec![];
+        }
+        let Some(next_ctx) = ctx.step(c) else {
+            return vec![];
+        };
+        let arabic = s.chars().next().filter(|&x| is_arabic_letter(x));
+        let latin_tsls = { arabic.is_none().then(|| self.table.alternatives(ctx, c)) }
+            .into_iter()
+            .flatten()
+            .map(|(tsl, after)| (tsl, tsl, after, false));
+        let arabic_tsls = match (normalization::fold_arabic(c), arabic) {
+            (None, _) if literal || arabic.is_some() || s.is_empty() => {
+                Some(("", "", &[][..], literal))
+            }
+            (Some(y), Some(x)) if y == x => {
+                Some((&s[..x.len_utf8()], &l[..c.len_utf8()], &[][..], true))
+            }
+            (Some(_), Some(_)) if c == SPACE || is_waqf_mark(c) => Some(("", "", &[][..], false)),
+            _ => None,
+        };
+        let tsls = latin_tsls.chain(arabic_tsls);
+        let tsl_results_iter = tsls.filter_map(|(tsl, expl, after, literal)| {
+            let ctx = next_ctx.clone().expect(after);
+            let rest = (v, w, &l[c.len_utf8()..]);
+            let s = s.strip_prefix(tsl)?;
+            let results = self.continue_encode(s, rest, &ctx, literal, find, lattice);
+            Some(results.into_iter().map(move |(mut q, found, mut e, path)| {
+                q.push(c);
+                e.push(expl);
+                (q, found, e, path)
+            }))
+        });
+        tsl_results_iter.flatten().collect()
+    }
+
+    /// Whether quran char `c` following `ctx` is silent when stopping at the end of its word,
+    /// i.e. a mark, or the alef after waw jama'a or fathatan.
+    fn is_silent_at_word_end(c: char, ctx: &Context) -> bool {
+        is_mark(c) || (c == LETTER_ALEF && matches!(ctx.last(), Some(LETTER_WAW | FATHATAN)))
+    }
+
+    /// Find the vertices below `(v, w, l)` whose following quran characters
+    /// satisfy the conditions pending in `ctx`, counting their locations allowed by `find`.
+    fn find_following(&self, (v, w, l): Edge, ctx: &Context, find: &FindOptions) -> Found {
+        match l.chars().next() {
+            _ if ctx.is_settled() => self.found_at(w, find),
+            Some(c) => { ctx.step(c) }
+                .map(|ctx| self.find_following((v, w, &l[c.len_utf8()..]), &ctx, find))
+                .unwrap_or_default(),
+            None if self.tree.edges_from(w).next().is_none() => match ctx.accepts_end() {
+                true => self.found_at(w, find),
+                false => vec![],
+            },
+            None => { self.tree.edges_from(w) }
+                .flat_map(|&e| self.find_following(e, ctx, find))
+                .collect(),
+        }
+    }
+
+    fn found_at(&self, v: usize, find: &FindOptions) -> Found {
+        match self.count_locations(v, find) {
+            0 => vec![],
+            n => vec![(v, n)],
+        }
+    }
+
+    fn count_locations(&self, v: usize, find: &FindOptions) -> usize {
+        match find.aya_start {
+            true => self.tree.count_aya_starts_in(v, &find.scope),
+            false => self.tree.count_data_in(v, &find.scope),
+        }
+    }
+
+    /// Find the vertices below `(v, w, l)` whose word ends right before `l`,
+    /// counting their locations allowed by `find`.
+    fn find_word_end(&self, (_, w, l): Edge, find: &FindOptions) -> Found {
+        match l.chars().next() {
+            Some(c) if is_separator(c) => self.found_at(w, find),
+            Some(_) => vec![],
+            None => { self.tree.edges_from(w) }
+                .flat_map(|&e| self.find_word_end(e, find))
+                .collect(),
+        }
+    }
+
+    /// Continue transliteration into harf muqottoah on the rest of `s` from `(v, w, l)`,
+    /// branching over the alternatives of the next segment of `lattice` when `s` is consumed.
+    fn continue_muqottoah(
+        &self,
+        s: &str,
+        (v, w, l): Edge,
+        find: &FindOptions,
+        lattice: &[Vec<String>],
+    ) -> PathResults<'_> {
+        match (s, lattice.split_first()) {
+            ("", Some((alternatives, lattice))) => { alternatives.iter().enumerate() }
+                .flat_map(|(k, s)| {
+                    { self.continue_muqottoah(s, (v, w, l), find, lattice) }
+                        .into_iter()
+                        .map(move |(q, found, e, mut path)| {
+                            path.push(k);
+                            (q, found, e, path)
+                        })
                 })
-            });
-            tsl_results_iter.flatten().collect()
+                .collect(),
+            ("", None) if !self.tree.vertices[w].2 => vec![],
+            ("", None) => {
+                let found = match find.whole_word {
+                    true => self.find_word_end((v, w, l), find),
+                    false => self.found_at(w, find),
+                };
+                match found.is_empty() {
+                    true => vec![],
+                    false => vec![(String::new(), found, vec![], vec![])],
+                }
+            }
+            _ => match l {
+                "" => { self.tree.edges_from(w) }
+                    .flat_map(|&e| self.rev_encode_muqottoah(s, e, find, lattice))
+                    .collect(),
+                _ => self.rev_encode_muqottoah(s, (v, w, l), find, lattice),
+            },
+        }
+    }
+
+    fn rev_encode_muqottoah(
+        &self,
+        s: &str,
+        (v, w, l): Edge,
+        find: &FindOptions,
+        lattice: &[Vec<String>],
+    ) -> PathResults<'_> {
+        let Some(c) = { l.chars().next() }.filter(|_| self.tree.overlaps(w, &find.scope)) else {
+            return vec![];
+        };
+        let tsls = self.table.harf_muqottoah_map(c).iter().map(String::as_str);
+        let tsl_results_iter = tsls.filter_map(|tsl| {
+            let s = s.strip_prefix(tsl)?;
+            let rest = (v, w, &l[c.len_utf8()..]);
+            let results = self.continue_muqottoah(s, rest, find, lattice);
+            Some(results.into_iter().map(move |(mut q, found, mut e, path)| {
+                q.push(c);
+                e.push(tsl);
+                (q, found, e, path)
+            }))
         });
-        results_iter.collect()
+        tsl_results_iter.flatten().collect()
     }
 
-    /// Find `s` in Alquran, returning a list of `Index`, where
+    /// Find `s` in Alquran, returning a list of `Index` in mushaf order, where
     /// `Index` is a tuple, containing:
     /// - `usize`: aya row / aya offset (`0..6236`)
     /// - `usize`: string offset in a specific aya (`0..length of aya`)
@@ -183,7 +607,215 @@ impl Quranize {
     /// assert_eq!(index, (5672, 0));
     /// ```
     pub fn find(&self, s: &str) -> Vec<Index> {
-        self.tree.find(s, 0)
+        self.find_with(s, &FindOptions::default())
+    }
+
+    /// Get the text of `m` extended to whole words, with `words_before` words before it
+    /// and `words_after` words after it, a.k.a. keyword in context.
+    /// The words are taken from the previous and next ayas of the sura
+    /// when the aya of `m` does not have enough of them.
+    /// Spaces and waqf marks between the words are kept in the texts before and after,
+    /// as are the waqf marks ending an aya when the words after reach its end.
+    /// Returns `None` when `m` is not a range of an aya, e.g. a hand-built one.
+    ///
+    /// # Examples
+    /// ```
+    /// let q = quranize::Quranize::new();
+    /// let m = q.find_matches("الرَّحمـٰنِ الرَّحيم")[0];
+    /// let context = q.context(&m, 1, 2).unwrap();
+    /// assert_eq!(context.before, [(0, "اللَّهِ ")]);
+    /// assert_eq!(context.text, "الرَّحمـٰنِ الرَّحيمِ");
+    /// assert_eq!(context.after, [(1, "الحَمدُ لِلَّهِ")]);
+    /// ```
+    pub fn context(
+        &self,
+        m: &Match,
+        words_before: usize,
+        words_after: usize,
+    ) -> Option<MatchContext<'_>> {
+        let aya = { self.saqs.get(m.row).map(|&(_, _, aya)| aya) }
+            .filter(|aya| aya.get(m.start..m.end).is_some())?;
+        let words = Vec::from_iter(Self::words(aya));
+        let last_word = m.word_end.checked_sub(1).and_then(|k| words.get(k));
+        let (start, end) = match (words.get(m.word_start), last_word) {
+            (Some(&(a, _)), Some(&(_, b))) if m.word_start < m.word_end => {
+                (a.min(m.start), b.max(m.end))
+            }
+            _ => (m.start, m.end),
+        };
+        let (mut before, mut after) = (vec![], vec![]);
+        let (mut row, mut offset, mut n) = (m.row, start, words_before);
+        loop {
+            let aya = self.saqs[row].2;
+            let words = Vec::from_iter(Self::words(&aya[..offset]));
+            let k = words.len().saturating_sub(n);
+            n -= words.len() - k;
+            let from = words.get(k).map_or(offset, |&(a, _)| a);
+            if from < offset {
+                before.push((row, &aya[from..offset]));
+            }
+            if n == 0 || row == 0 || self.saqs[row - 1].0 != self.saqs[row].0 {
+                break;
+            }
+            (row, offset) = (row - 1, self.saqs[row - 1].2.len());
+        }
+        before.reverse();
+        let (mut row, mut offset, mut n) = (m.row, end, words_after);
+        loop {
+            let aya = self.saqs[row].2;
+            let words = Vec::from_iter(Self::words(&aya[offset..]));
+            let k = n.min(words.len());
+            n -= k;
+            let to = match k.checked_sub(1) {
+                _ if k == words.len() => aya.len(),
+                Some(k) => offset + words[k].1,
+                None => offset,
+            };
+            if offset < to {
+                after.push((row, &aya[offset..to]));
+            }
+            if n == 0 || row + 1 == AYA_COUNT || self.saqs[row + 1].0 != self.saqs[row].0 {
+                break;
+            }
+            (row, offset) = (row + 1, 0);
+        }
+        Some(MatchContext {
+            before,
+            text: &aya[start..end],
+            after,
+        })
+    }
+
+    /// Find `s` in Alquran like [`Quranize::find`], keeping only the locations allowed by `options`.
+    ///
+    /// # Examples
+    /// ```
+    /// let q = quranize::Quranize::new();
+    /// let options = quranize::FindOptions { whole_word: true, ..Default::default() };
+    /// assert!(q.find("قُل").len() > q.find_with("قُل", &options).len());
+    /// let options = quranize::FindOptions { aya_start: true, ..Default::default() };
+    /// assert_eq!(q.find_with("عَمَّ", &options), [(5672, 0)]);
+    /// ```
+    pub fn find_with(&self, s: &str, options: &FindOptions) -> Vec<Index> {
+        let mut locations = self.tree.find(s, 0, &options.scope);
+        locations.sort();
+        locations.retain(|&(i, j)| {
+            (!options.aya_start || j == 0)
+                && (!options.whole_word || self.is_word_end(i, j + s.len()))
+        });
+        locations
+    }
+
+    /// Whether byte offset `k` of aya row `i` is the end of a word.
+    fn is_word_end(&self, i: usize, k: usize) -> bool {
+        self.saqs[i].2[k..].chars().next().is_none_or(is_separator)
+    }
+
+    /// Find `s` in Alquran like [`Quranize::find`], also across the ends of consecutive ayas
+    /// of a sura, as when reciting continuously, where the ayas are separated by a space in `s`.
+    /// Returns a list of location spans `(start row, start offset)..(end row, end offset)`,
+    /// where the end offset is the byte offset right after the match in the end row.
+    /// The whole Alquran is searched, as [`Scope`] does not apply to spans across ayas;
+    /// filter the spans with [`Scope::contains`] to keep the ones of some ayas.
+    ///
+    /// # Examples
+    /// ```
+    /// let q = quranize::Quranize::new();
+    /// assert_eq!(q.find_across_ayas("الم ذٰلِكَ الكِتابُ"), [((7, 0), (8, 29))]);
+    /// assert!(q.find("الم ذٰلِكَ الكِتابُ").is_empty());
+    /// ```
+    pub fn find_across_ayas(&self, s: &str) -> Vec<(Index, Index)> {
+        let within_ayas = { self.find(s).into_iter() }.map(|(i, j)| ((i, j), (i, j + s.len())));
+        let across_ayas = { s.match_indices(SPACE) }.flat_map(|(k, _)| {
+            let (head, tail) = (&s[..k], &s[k + SPACE.len_utf8()..]);
+            { self.find(head).into_iter() }
+                .filter(|&(i, j)| j + head.len() == self.saqs[i].2.len())
+                .filter_map(move |(i, j)| Some(((i, j), self.find_aya_prefix(i + 1, tail)?)))
+        });
+        let mut spans = Vec::from_iter(within_ayas.chain(across_ayas));
+        spans.sort();
+        spans.dedup();
+        spans
+    }
+
+    /// Find `s` at the start of aya `i` and the following ayas of its sura,
+    /// returning the location right after `s`.
+    fn find_aya_prefix(&self, i: usize, s: &str) -> Option<Index> {
+        let &(sura, _, aya) = self.saqs.get(i)?;
+        if self.saqs[i - 1].0 != sura || s.is_empty() {
+            return None;
+        }
+        if aya.starts_with(s) {
+            return Some((i, s.len()));
+        }
+        let s = s.strip_prefix(aya)?.strip_prefix(SPACE)?;
+        self.find_aya_prefix(i + 1, s)
+    }
+
+    /// Find `s` in Alquran, ignoring harakat and other marks, and folding orthographic variants
+    /// as in [`ArabicFolding::default`], e.g. to find Arabic text typed without tashkeel.
+    /// Returns a list of tuple:
+    /// - `usize`: aya row / aya offset (`0..6236`)
+    /// - `usize`: start of the matched string in the aya
+    /// - `usize`: end of the matched string in the aya, including the marks of its last letter
+    ///
+    /// # Examples
+    /// ```
+    /// let q = quranize::Quranize::new();
+    /// let (i, start, end) = q.find_rasm("عم يتساءلون")[0];
+    /// assert_eq!(&q.get_quran(i).unwrap()[start..end], "عَمَّ يَتَساءَلونَ");
+    /// ```
+    pub fn find_rasm(&self, s: &str) -> Vec<(usize, usize, usize)> {
+        self.find_rasm_with(s, &FindOptions::default())
+    }
+
+    /// Find `s` in Alquran like [`Quranize::find_rasm`], keeping only the locations allowed by `options`.
+    ///
+    /// # Examples
+    /// ```
+    /// use quranize::{FindOptions, Scope};
+    /// let q = quranize::Quranize::new();
+    /// let options = FindOptions { scope: Scope::suras(&[114]), ..Default::default() };
+    /// assert_eq!(q.find_rasm_with("قل اعوذ", &options), [(6230, 0, 19)]);
+    /// ```
+    pub fn find_rasm_with(&self, s: &str, options: &FindOptions) -> Vec<(usize, usize, usize)> {
+        self.find_folded_with(s, &ArabicFolding::default(), options)
+    }
+
+    /// Find `s` in Alquran like [`Quranize::find_rasm`], folding both `s` and quran text with `folding`.
+    ///
+    /// # Examples
+    /// ```
+    /// let q = quranize::Quranize::new();
+    /// let folding = quranize::ArabicFolding { marks: false, ..Default::default() };
+    /// assert_eq!(q.find_folded("قُل هُوَ اللَّهُ اَحَدٌ", &folding), [(6221, 0, 43)]);
+    /// assert_eq!(q.find_folded("قل هو الله احد", &folding), []);
+    /// ```
+    pub fn find_folded(&self, s: &str, folding: &ArabicFolding) -> Vec<(usize, usize, usize)> {
+        self.find_folded_with(s, folding, &FindOptions::default())
+    }
+
+    /// Find `s` in Alquran like [`Quranize::find_folded`], keeping only the locations allowed by `options`.
+    pub fn find_folded_with(
+        &self,
+        s: &str,
+        folding: &ArabicFolding,
+        options: &FindOptions,
+    ) -> Vec<(usize, usize, usize)> {
+        let s = folding.normalize(s);
+        let mut locations = Vec::from_iter(
+            {
+                self.tree
+                    .find_folded(&s, &|c| folding.fold(c), &options.scope)
+            }
+            .into_iter()
+            .map(|((i, j), len)| (i, j, j + len)),
+        );
+        locations.sort();
+        locations.retain(|&(i, j, k)| {
+            (!options.aya_start || j == 0) && (!options.whole_word || self.is_word_end(i, k))
+        });
+        locations
     }
 
     /// Maps `i` into sura number, where `i` is an aya row / aya offset (`0..6236`).
@@ -197,6 +829,18 @@ impl Quranize {
         Some(self.saqs.get(i)?.0)
     }
 
+    /// Maps `i` into juz number, where `i` is an aya row / aya offset (`0..6236`).
+    ///
+    /// # Examples
+    /// ```
+    /// let q = quranize::Quranize::new();
+    /// assert_eq!(q.get_juz(5672), Some(30));
+    /// assert_eq!(q.get_juz(5671), Some(29));
+    /// ```
+    pub fn get_juz(&self, i: usize) -> Option<u8> {
+        (i < AYA_COUNT).then(|| JUZ_STARTS.partition_point(|&j| j <= i) as u8)
+    }
+
     /// Maps `i` into aya number, where `i` is an aya row / aya offset (`0..6236`).
     ///
     /// # Examples
@@ -226,12 +870,12 @@ impl Quranize {
 
         for c in s.chars() {
             if harf_muqottoah {
-                if let Some(tsl) = harf_muqottoah_map(c).first() {
+                if let Some(tsl) = self.table.harf_muqottoah_map(c).first() {
                     buffer.push_str(tsl);
                     harf_muqottoah = false;
                 }
             } else {
-                if let Some(tsl) = map(c).first() {
+                if let Some(tsl) = self.table.map(c).first() {
                     buffer.push_str(tsl);
                 } else {
                     // Push the character as-is if no mapping found
@@ -322,7 +966,14 @@ mod tests {
 
     impl Quranize {
         fn e(&self, text: &str) -> Vec<String> {
-            self.encode(text).into_iter().map(|r| r.0).collect()
+            self.e_with(text, &EncodeOptions::default())
+        }
+
+        fn e_with(&self, text: &str, options: &EncodeOptions) -> Vec<String> {
+            self.encode_with(text, options)
+                .into_iter()
+                .map(|r| r.0)
+                .collect()
         }
     }
 
@@ -415,6 +1066,30 @@ mod tests {
         assert_eq!(q.e("alif lam ro"), &["الر"]);
     }
 
+    #[test]
+    fn test_contextual_rules() {
+        let q = Quranize::new();
+        assert_eq!(q.encode("majre"), [("مَجر".to_string(), 1, vec!["m", "a", "j", "re"])]);
+        assert_eq!(q.e("majreeha"), ["مَجراها"]);
+        assert_eq!(q.e("kitabi")[0], "الكِتابِ");
+        assert!(q.e("qolu").contains(&"قالو".to_string()));
+        assert!(!q.e("qou").contains(&"قالو".to_string()));
+    }
+
+    #[test]
+    fn test_tajweed() {
+        let q = Quranize::new();
+        assert_eq!(q.e("mimba'di")[0], "مِن بَعدِ");
+        assert_eq!(q.e("ayyaqul"), ["أَن يَقول"]);
+        assert_eq!(q.e("hudallilmuttaqin"), ["هُدًى لِلمُتَّقين"]);
+        assert_eq!(q.e("samiumbasir"), ["سَميعٌ بَصير"]);
+        assert_eq!(q.e("mingkum")[0], "مِنكُم");
+        assert_eq!(q.e("lam yalide"), ["لَم يَلِد"]);
+        let count = |s| q.encode(s).into_iter().find(|r| r.0 == "أَحَد").map(|r| r.1);
+        assert_eq!(count("ahade"), Some(6));
+        assert!(count("ahad") > Some(6));
+    }
+
     #[test]
     fn test_quranize_empty_result() {
         let q = Quranize::new();
@@ -440,6 +1115,7 @@ mod tests {
         let q = Quranize::new();
         assert!(q.find("بِسمِ").contains(&(0, 0)));
         assert_eq!(q.find("وَالنّاسِ").last(), Some(&(6235, 28)));
+        assert!(q.find("وَالنّاسِ").is_sorted());
         assert!(q.find("الم").contains(&(7, 0)));
         assert_eq!(q.find("بِسمِ اللَّهِ الرَّحمـٰنِ الرَّحيمِ").len(), 2);
         assert!(q.find("").is_empty());
@@ -453,6 +1129,422 @@ mod tests {
         assert_eq!(q.find("abc").first(), None);
     }
 
+    #[test]
+    fn test_encode_arabic() {
+        let q = Quranize::new();
+        let e = |s| Vec::from_iter(q.encode_arabic(s).into_iter().map(|r| (r.0, r.1)));
+        assert_eq!(
+            e("بسم الله الرحمن الرحيم"),
+            [("بِسمِ اللَّهِ الرَّحمـٰنِ الرَّحيم".to_string(), 2)]
+        );
+        assert_eq!(
+            e("قل اعوذ برب الناس"),
+            [("قُل أَعوذُ بِرَبِّ النّاس".to_string(), 1)]
+        );
+        assert_eq!(e("الي ربك")[0].0, "إِلىٰ رَبِّك");
+        assert_eq!(
+            q.encode_arabic("قل اعوذ")[0].2,
+            ["ق", "", "ل", "", "أ", "", "ع", "و", "ذ"],
+        );
+        assert!(q.encode_arabic("").is_empty());
+        assert!(q.encode_arabic("bismillah").is_empty());
+        assert!(q.encode_arabic("قلقلقل").is_empty());
+    }
+
+    #[test]
+    fn test_encode_mixed_script() {
+        let q = Quranize::new();
+        assert_eq!(q.e("قل huwallahu ahad"), ["قُل هُوَ اللَّهُ أَحَد"]);
+        assert_eq!(q.e("qul هو الله ahad"), ["قُل هُوَ اللَّهُ أَحَد"]);
+        assert_eq!(q.e("bismillah الرحمن الرحيم"), ["بِسمِ اللَّهِ الرَّحمـٰنِ الرَّحيم"]);
+        assert_eq!(
+            q.encode("قل huwa")[0].2,
+            ["ق", "", "ل", "", "h", "u", "w", "a"],
+        );
+        assert!(q.e("قل xyz").is_empty());
+    }
+
+    #[test]
+    fn test_academic_transliteration() {
+        let q = Quranize::new();
+        assert_eq!(
+            q.e("al-ḥamdu lillāhi rabbi l-ʿālamīn"),
+            ["الحَمدُ لِلَّهِ رَبِّ العالَمين"],
+        );
+        assert_eq!(
+            q.e("bismi llāhi r-raḥmāni r-raḥīm"),
+            ["بِسمِ اللَّهِ الرَّحمـٰنِ الرَّحيم"]
+        );
+        assert_eq!(q.e("qul huwa llāhu aḥad"), ["قُل هُوَ اللَّهُ أَحَد"]);
+        assert_eq!(q.e("fīhi"), q.e("fiihi"));
+        assert_eq!(q.e("qūl"), ["قول"]);
+        assert!(q.e("fīhi").contains(&"فيهِ".to_string()));
+        let plain = q.e("sirot");
+        let academic = q.e("ṣirāṭ");
+        assert!(academic.len() < plain.len());
+        assert!(academic.iter().all(|r| plain.contains(r)));
+        assert!(academic.iter().all(|r| r.starts_with('ص')));
+        assert_eq!(q.e("ḏālika"), ["ذٰلِكَ"]);
+        assert_eq!(q.e("ṯumma"), ["ثُمَّ"]);
+        assert_eq!(q.e("šayʾ")[0], "شَيء");
+        assert!(q.e("ġafūr").contains(&"غَفور".to_string()));
+        assert!(q.e("ḫayr").contains(&"خَير".to_string()));
+        assert!(q.e("ǧannāt").contains(&"جَنّات".to_string()));
+    }
+
+    #[test]
+    fn test_arabizi() {
+        let q = Quranize::new();
+        let options = EncodeOptions {
+            arabizi: true,
+            ..Default::default()
+        };
+        let e = |s| q.e_with(s, &options);
+        assert_eq!(e("al7amdulillah"), ["الحَمدُ لِلَّه"]);
+        assert_eq!(e("ya2ayyuhannas")[0], "يا أَيُّهَا النّاس");
+        assert_eq!(e("rabbil 3alamin"), ["رَبِّ العالَمين"]);
+        assert_eq!(e("9iro6"), ["صِراط"]);
+        assert_eq!(e("5alaqa")[0], "خَلَقَ");
+        assert!(e("8").is_empty());
+        assert_eq!(q.e("al7amdulillah"), q.e("alamdulillah"));
+    }
+
+    #[test]
+    fn test_case_sensitive() {
+        let q = Quranize::new();
+        let options = EncodeOptions {
+            case_sensitive: true,
+            ..Default::default()
+        };
+        let e = |s| q.e_with(s, &options);
+        assert_eq!(e("Sirotim mustaqim"), ["صِراطٍ مُستَقيم"]);
+        assert_eq!(e("alHamdu"), ["الحَمدُ"]);
+        assert!(e("Dolla").len() < q.e("dolla").len());
+        assert!(e("wadDuHa")
+            .iter()
+            .all(|r| r.contains('ض') && r.contains('ح')));
+        assert!(!e("wadDuHa").is_empty());
+        assert_eq!(e("Tolaqa"), Vec::<String>::new());
+        assert_eq!(q.e("AlHamdu"), q.e("alhamdu"));
+        assert_eq!(e("Alif Lam Mim"), q.e("alif lam mim"));
+    }
+
+    #[test]
+    fn test_word_breaks() {
+        assert_eq!(
+            Quranize::word_breaks("", normalization::normalize),
+            [] as [usize; 0]
+        );
+        assert_eq!(
+            Quranize::word_breaks("walam yakun", normalization::normalize),
+            [5]
+        );
+        assert_eq!(
+            Quranize::word_breaks(" wa - lam  yakun ", normalization::normalize),
+            [2, 5]
+        );
+        assert_eq!(
+            Quranize::word_breaks("قل huwa", normalization::normalize),
+            [2]
+        );
+    }
+
+    #[test]
+    fn test_spaces() {
+        let q = Quranize::new();
+        let strict = EncodeOptions {
+            strict_spaces: true,
+            ..Default::default()
+        };
+        let e = |s| q.e_with(s, &strict);
+        assert_eq!(e("walam yakun"), ["وَلَم يَكُن"]);
+        assert!(e("wala myakun").is_empty());
+        assert_eq!(q.e("wala myakun"), ["وَلَم يَكُن"]);
+        assert_eq!(e("walamyakun"), ["وَلَم يَكُن"]);
+        assert_eq!(e("alif lam mim"), ["الم"]);
+        assert_eq!(q.e("bis millah"), ["بِسمِ اللَّه"]);
+        assert!(e("bis millah").is_empty());
+        assert!(q.e("ma lik")[0].starts_with("ما "));
+        assert!(!q.e("mali k")[0].starts_with("ما "));
+        for s in ["alla hu", "ma a", "alif lam mim", "bis millah"] {
+            let results = q.e(s);
+            let forms = std::collections::HashSet::<&String>::from_iter(&results);
+            assert_eq!(forms.len(), results.len());
+        }
+    }
+
+    #[test]
+    fn test_location_counts() {
+        let q = Quranize::new();
+        let whole_word = FindOptions {
+            whole_word: true,
+            ..Default::default()
+        };
+        let options = EncodeOptions {
+            find: whole_word.clone(),
+            ..Default::default()
+        };
+        for s in [
+            "annas",
+            "arrohman",
+            "assamad",
+            "khoirun nuzulan",
+            "ahadun",
+            "ghofurur rohim",
+        ] {
+            let results = q.encode(s);
+            assert!(!results.is_empty());
+            for (form, n, _) in results {
+                assert_eq!(n, q.find(&form).len(), "{}", form);
+            }
+            for (form, n, _) in q.encode_with(s, &options) {
+                assert_eq!(n, q.find_with(&form, &whole_word).len(), "{}", form);
+            }
+        }
+    }
+
+    #[test]
+    fn test_whole_word() {
+        let q = Quranize::new();
+        let whole_word = FindOptions {
+            whole_word: true,
+            ..Default::default()
+        };
+        let options = EncodeOptions {
+            find: whole_word.clone(),
+            ..Default::default()
+        };
+        let e = |s| q.e_with(s, &options);
+        assert_eq!(q.e("wa'tasimu")[0], "وَاعتَصِمو");
+        assert_eq!(e("wa'tasimu"), ["وَاعتَصِموا"]);
+        assert!(q.e("alhamdulil").contains(&"الحَمدُ لِل".to_string()));
+        assert!(e("alhamdulil").is_empty());
+        assert_eq!(e("alhamdulillah")[0], "الحَمدُ لِلَّهِ");
+        assert!(e("ma").contains(&"ما".to_string()));
+        assert!(!e("ma").contains(&"مَعَ".to_string()));
+        assert!(!e("sa").contains(&"سائِلٌ".to_string()));
+        assert_eq!(
+            q.encode_with("qul", &options)[0].1,
+            q.find_with("قُل", &whole_word).len()
+        );
+        assert!(q.find_with("قُل", &whole_word).len() < q.find("قُل").len());
+        let ahad = { q.encode_with("ahad", &options).into_iter() }
+            .find(|(q, _, _)| q == "أَحَدٌ")
+            .unwrap();
+        assert_eq!(ahad.1, q.find_with("أَحَدٌ", &whole_word).len());
+        assert!(q.find_with("وَالنّاسِ", &whole_word).contains(&(6235, 28)));
+        assert_eq!(q.find_with("النّا", &whole_word), []);
+    }
+
+    #[test]
+    fn test_find_across_ayas() {
+        let q = Quranize::new();
+        let aya = |i| q.get_quran(i).unwrap();
+        let s = format!("{} {}", &aya(0)[26..], &aya(1)[..14]);
+        assert_eq!(q.find_across_ayas(&s), [((0, 26), (1, 14))]);
+        let s = format!("{} {} {}", aya(4901), aya(4902), &aya(4903)[..12]);
+        assert_eq!(q.find_across_ayas(&s), [((4901, 0), (4903, 12))]);
+        let s = format!("{} {}", aya(6229), aya(6230));
+        assert_eq!(q.find_across_ayas(&s), []);
+        let s = format!("{} {}", aya(6), aya(7));
+        assert_eq!(q.find_across_ayas(&s), []);
+        let s = aya(6231);
+        assert_eq!(q.find_across_ayas(s), [((6231, 0), (6231, s.len()))]);
+        assert_eq!(q.find_across_ayas(""), []);
+    }
+
+    #[test]
+    fn test_context() {
+        let q = Quranize::new();
+        let m = q
+            .find_matches("وَالنّاسِ")
+            .into_iter()
+            .find(|m| m.row == 6235)
+            .unwrap();
+        let context = q.context(&m, 0, 0).unwrap();
+        assert_eq!(
+            (context.before, context.text, context.after),
+            (vec![], "وَالنّاسِ", vec![])
+        );
+        let context = q.context(&m, 5, 1).unwrap();
+        assert_eq!(
+            context.before,
+            [(6234, "في صُدورِ النّاسِ"), (6235, "مِنَ الجِنَّةِ ")]
+        );
+        assert_eq!(context.after, []);
+
+        let m = q.get_match(0, 0, 13).unwrap();
+        let context = q.context(&m, 1, usize::MAX).unwrap();
+        assert_eq!(context.before, []);
+        assert_eq!(context.text, "بِسمِ اللَّهِ");
+        assert_eq!(context.after.len(), 7);
+        assert_eq!(context.after[0], (0, " الرَّحمـٰنِ الرَّحيمِ"));
+        assert_eq!(context.after[6], (6, q.get_quran(6).unwrap()));
+
+        let i = (0..AYA_COUNT)
+            .find(|&i| q.get_quran(i).unwrap().contains(" ۖ "))
+            .unwrap();
+        let aya = q.get_quran(i).unwrap();
+        let k = aya.find(" ۖ ").unwrap();
+        let m = q.get_match(i, k + " ۖ ".len(), k + " ۖ ".len() + 2).unwrap();
+        let context = q.context(&m, 1, 0).unwrap();
+        assert!(context.before[0].1.ends_with(" ۖ "));
+        assert!(!context.text.starts_with(' '));
+        assert_eq!(context.text.split(' ').count(), 1);
+
+        let aya = q.get_quran(5350).unwrap();
+        assert!(aya.ends_with(" ۜ"));
+        let m = q
+            .find_matches("عَنّي")
+            .into_iter()
+            .find(|m| m.row == 5350)
+            .unwrap();
+        let context = q.context(&m, m.word_start, usize::MAX).unwrap();
+        let (before, after) = (context.before[0], context.after[0]);
+        assert_eq!((before.0, after.0), (5350, 5350));
+        assert_eq!(format!("{}{}{}", before.1, context.text, after.1), aya);
+        assert_eq!(q.context(&m, 0, 1).unwrap().after, [(5350, " مالِيَه ۜ")]);
+
+        let m = Match {
+            row: AYA_COUNT,
+            ..m
+        };
+        assert_eq!(q.context(&m, 1, 1), None);
+        let m = q.get_match(0, 0, 13).unwrap();
+        let m = Match { end: 200, ..m };
+        assert_eq!(q.context(&m, 1, 1), None);
+        let m = Match { end: 1, ..m };
+        assert_eq!(q.context(&m, 1, 1), None);
+    }
+
+    #[test]
+    fn test_scope() {
+        let q = Quranize::new();
+        let in_scope = |scope: Scope| FindOptions {
+            scope,
+            ..Default::default()
+        };
+        let juz_amma = in_scope(Scope::juz(30));
+        let locations = q.find_with("قُل", &juz_amma);
+        assert!(!locations.is_empty());
+        assert!(locations.iter().all(|&(i, _)| q.get_juz(i) == Some(30)));
+        assert_eq!(
+            locations,
+            Vec::from_iter(q.find("قُل").into_iter().filter(|&(i, _)| i >= 5672))
+        );
+        let options = EncodeOptions {
+            find: juz_amma.clone(),
+            ..Default::default()
+        };
+        let (quran, count, _) = &q.encode_with("qul huwallahu ahad", &options)[0];
+        assert_eq!((quran.as_str(), *count), ("قُل هُوَ اللَّهُ أَحَد", 1));
+        let (quran, count, _) = &q.encode_with("qul", &options)[0];
+        assert_eq!(*count, q.find_with(quran, &juz_amma).len());
+        assert!(*count < q.find(quran).len());
+        let (_, count, _) = &q.encode_with("alif lam mim", &options)[0];
+        assert_eq!(*count, q.find_with("الم", &juz_amma).len());
+        let options = EncodeOptions {
+            find: in_scope(Scope::suras(&[2, 3])),
+            ..Default::default()
+        };
+        assert_eq!(
+            q.encode_with("alif lam mim", &options)[0].1,
+            q.find_with("الم", &options.find).len()
+        );
+        assert!(q.encode_with("qul huwallahu ahad", &options).is_empty());
+        assert_eq!(q.find_with("بِسمِ", &in_scope(Scope::rows(0..1))), [(0, 0)]);
+        let mut locations = q.find_rasm("قل");
+        locations.retain(|&(i, _, _)| i >= 5672);
+        assert_eq!(q.find_rasm_with("قل", &juz_amma), locations);
+        let whole_word = FindOptions {
+            whole_word: true,
+            ..juz_amma
+        };
+        let locations = q.find_rasm_with("قل", &whole_word);
+        assert_eq!(locations.len(), q.find_with("قُل", &whole_word).len());
+    }
+
+    #[test]
+    fn test_aya_start() {
+        let q = Quranize::new();
+        let aya_start = FindOptions {
+            aya_start: true,
+            ..Default::default()
+        };
+        let options = EncodeOptions {
+            find: aya_start.clone(),
+            ..Default::default()
+        };
+        let counts = |s| Vec::from_iter(q.encode_with(s, &options).into_iter().map(|r| r.1));
+        assert_eq!(counts("qul huwallahu ahad"), [1]);
+        assert!(q.encode_with("ahad", &options).is_empty());
+        assert_eq!(counts("qul a'udzu"), [2]);
+        assert_eq!(q.find_with("قُل أَعوذُ", &aya_start), [(6225, 0), (6230, 0)]);
+        assert!(q
+            .find_with("عَلَيهِم", &aya_start)
+            .iter()
+            .all(|&(_, j)| j == 0));
+    }
+
+    #[test]
+    fn test_harf_muqottoah_options() {
+        let q = Quranize::new();
+        let whole_word = FindOptions {
+            whole_word: true,
+            ..Default::default()
+        };
+        let aya_start = FindOptions {
+            aya_start: true,
+            ..Default::default()
+        };
+        for find in [whole_word, aya_start] {
+            let options = EncodeOptions {
+                find: find.clone(),
+                ..Default::default()
+            };
+            for s in ["alif lam mim", "alif lam ro"] {
+                let (form, n, _) = q.encode_with(s, &options).remove(0);
+                assert_eq!(n, q.find_with(&form, &find).len());
+            }
+        }
+        let options = EncodeOptions {
+            find: FindOptions {
+                whole_word: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert_eq!(q.encode_with("alif lam mim", &options)[0].1, 6);
+    }
+
+    #[test]
+    fn test_find_rasm() {
+        let q = Quranize::new();
+        let texts = |s| {
+            let results = q.find_rasm(s).into_iter();
+            Vec::from_iter(results.map(|(i, j, k)| (i, &q.get_quran(i).unwrap()[j..k])))
+        };
+        let basmalah = "بِسمِ اللَّهِ الرَّحمـٰنِ الرَّحيمِ";
+        assert_eq!(
+            texts("بسم الله الرحمن الرحيم"),
+            [(0, basmalah), (3188, basmalah)]
+        );
+        assert_eq!(
+            texts("بِسم الله الرحمن الرحيم"),
+            texts("بسم الله الرحمن الرحيم")
+        );
+        assert_eq!(texts("والناس").last(), Some(&(6235, "وَالنّاسِ")));
+        assert_eq!(texts("عليهم غير")[0].1, "عَلَيهِم غَيرِ");
+        assert!(q
+            .find_rasm("الرحمن الرحيم")
+            .contains(&(0, 26, basmalah.len())));
+        assert_eq!(texts("إِلى ربك"), texts("الي ربك"));
+        assert_eq!(texts("ذلک الکتاب"), [(8, "ذٰلِكَ الكِتابُ")]);
+        assert_eq!(texts("رحمة"), texts("رحمه"));
+        assert!(q.find_rasm("").is_empty());
+        assert!(q.find_rasm("abc").is_empty());
+    }
+
     #[test]
     fn test_tree_props() {
         let t = Quranize::new().tree;
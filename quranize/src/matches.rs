@@ -0,0 +1,111 @@
+use crate::transliteration::chars::{is_arabic_letter, SPACE};
+use crate::Quranize;
+
+/// Location of a text found in an aya.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Match {
+    /// Aya row / aya offset (`0..6236`).
+    pub row: usize,
+    /// Byte offset of the start of the text in the aya.
+    pub start: usize,
+    /// Byte offset right after the end of the text in the aya.
+    pub end: usize,
+    /// Char offset of the start of the text in the aya.
+    pub char_start: usize,
+    /// Char offset right after the end of the text in the aya.
+    pub char_end: usize,
+    /// Number of the word where the text starts, counting the words of the aya from 0.
+    pub word_start: usize,
+    /// Number of the word right after the word where the text ends.
+    pub word_end: usize,
+}
+
+impl Quranize {
+    /// Find `s` in Alquran like [`Quranize::find`], returning the [`Match`] of each location.
+    ///
+    /// # Examples
+    /// ```
+    /// let q = quranize::Quranize::new();
+    /// let m = q.find_matches("يَتَساءَلونَ").into_iter().find(|m| m.row == 5672).unwrap();
+    /// assert_eq!((m.start, m.end), (11, 35));
+    /// assert_eq!((m.char_start, m.char_end), (6, 18));
+    /// assert_eq!((m.word_start, m.word_end), (1, 2));
+    /// ```
+    pub fn find_matches(&self, s: &str) -> Vec<Match> {
+        { self.find(s).into_iter() }
+            .filter_map(|(i, j)| self.get_match(i, j, j + s.len()))
+            .collect()
+    }
+
+    /// Get the [`Match`] of the text from byte offset `start` to `end` of aya row `i`,
+    /// e.g. of the results of [`Quranize::find_rasm`].
+    /// Returns `None` when the offsets are not char boundaries of the aya.
+    ///
+    /// # Examples
+    /// ```
+    /// let q = quranize::Quranize::new();
+    /// let (i, start, end) = q.find_rasm("الرحمن الرحيم")[0];
+    /// let m = q.get_match(i, start, end).unwrap();
+    /// assert_eq!((m.word_start, m.word_end), (2, 4));
+    /// assert_eq!(q.get_match(0, 0, 1), None);
+    /// ```
+    pub fn get_match(&self, i: usize, start: usize, end: usize) -> Option<Match> {
+        let aya = self.get_quran(i)?;
+        let (before, text) = (aya.get(..start)?, aya.get(start..end)?);
+        let words = Vec::from_iter(Self::words(aya));
+        Some(Match {
+            row: i,
+            start,
+            end,
+            char_start: before.chars().count(),
+            char_end: before.chars().count() + text.chars().count(),
+            word_start: words.iter().filter(|&&(_, b)| b <= start).count(),
+            word_end: words.iter().filter(|&&(a, _)| a < end).count(),
+        })
+    }
+
+    /// Byte ranges of the words of `aya`, i.e. its space-separated parts having an Arabic letter.
+    pub(super) fn words(aya: &str) -> impl Iterator<Item = (usize, usize)> + '_ {
+        { aya.split(SPACE) }
+            .scan(0, |offset, w| {
+                let start = *offset;
+                *offset += w.len() + SPACE.len_utf8();
+                Some((start, start + w.len(), w))
+            })
+            .filter(|(_, _, w)| w.chars().any(is_arabic_letter))
+            .map(|(a, b, _)| (a, b))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AYA_COUNT;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_find_matches() {
+        let q = Quranize::new();
+        let aya = q.get_quran(6235).unwrap();
+        let m = *q.find_matches("وَالنّاسِ").last().unwrap();
+        assert_eq!((m.row, m.start, m.end), (6235, 28, aya.len()));
+        assert_eq!(&aya[m.start..m.end], "وَالنّاسِ");
+        assert_eq!(m.char_end - m.char_start, "وَالنّاسِ".chars().count());
+        assert_eq!((m.word_start, m.word_end), (2, 3));
+        assert_eq!(q.find_matches("وَالنّاسِ").len(), q.find("وَالنّاسِ").len());
+        assert_eq!(q.find_matches(""), []);
+        let i = (0..AYA_COUNT)
+            .find(|&i| q.get_quran(i).unwrap().contains(" ۖ "))
+            .unwrap();
+        let aya = q.get_quran(i).unwrap();
+        let start = aya.find(" ۖ ").unwrap() + " ۖ ".len();
+        let m = q.get_match(i, start, aya.len()).unwrap();
+        assert_eq!(m.word_start, aya[..start].split(' ').count() - 2);
+        assert_eq!(m.word_end, aya.split(' ').count() - 1);
+        let m = q.get_match(i, 0, 0).unwrap();
+        assert_eq!((m.word_start, m.word_end), (0, 0));
+        assert_eq!(q.get_match(AYA_COUNT, 0, 0), None);
+        assert_eq!(q.get_match(0, 2, 1), None);
+    }
+}
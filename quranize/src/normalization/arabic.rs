@@ -0,0 +1,114 @@
+use crate::transliteration::chars::*;
+
+/// Orthographic variants of Arabic letters folded into one letter when finding Arabic text,
+/// so that text typed on common keyboards matches quran text.
+///
+/// Folding is applied on both the text being searched for and the quran text,
+/// while the locations found are still those in the quran text.
+/// Harakat and other marks are ignored when `marks` is set.
+///
+/// # Examples
+///
+/// ```
+/// let folding = quranize::ArabicFolding::default();
+/// assert_eq!(folding.normalize("إِلىٰ الجَنَّةِ"), "الي الجنه");
+///
+/// let folding = quranize::ArabicFolding { marks: false, ..Default::default() };
+/// assert_eq!(folding.normalize("إِلىٰ"), "اِليٰ");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ArabicFolding {
+    /// Ignore harakat, shadda, superscript alef, hamza above and tatweel.
+    pub marks: bool,
+    /// Fold alef with hamza or madda, and alef wasla, into alef.
+    pub alef: bool,
+    /// Fold waw with hamza above and yeh with hamza above into waw and yeh.
+    pub hamza: bool,
+    /// Fold alef maksura into yeh.
+    pub alef_maksura: bool,
+    /// Fold teh marbuta into heh.
+    pub teh_marbuta: bool,
+    /// Fold Persian yeh and keheh, also used in Urdu, into Arabic yeh and kaf.
+    pub persian: bool,
+    /// Fold Urdu yeh barree, heh goal, heh doachashmee and teh marbuta goal into Arabic letters.
+    pub urdu: bool,
+}
+
+impl Default for ArabicFolding {
+    fn default() -> Self {
+        Self {
+            marks: true,
+            alef: true,
+            hamza: true,
+            alef_maksura: true,
+            teh_marbuta: true,
+            persian: true,
+            urdu: true,
+        }
+    }
+}
+
+impl ArabicFolding {
+    /// Fold `c`, returning `None` when `c` is ignored.
+    pub(crate) fn fold(&self, c: char) -> Option<char> {
+        match c {
+            c if self.marks && is_mark(c) => None,
+            LETTER_ALEF_WITH_MADDA_ABOVE
+            | LETTER_ALEF_WITH_HAMZA_ABOVE
+            | LETTER_ALEF_WITH_HAMZA_BELOW
+            | '\u{0671}'
+                if self.alef =>
+            {
+                Some(LETTER_ALEF)
+            }
+            LETTER_WAW_WITH_HAMZA_ABOVE if self.hamza => Some(LETTER_WAW),
+            LETTER_YEH_WITH_HAMZA_ABOVE if self.hamza => Some(LETTER_YEH),
+            LETTER_ALEF_MAKSURA if self.alef_maksura => Some(LETTER_YEH),
+            LETTER_TEH_MARBUTA if self.teh_marbuta => Some(LETTER_HEH),
+            '\u{06CC}' if self.persian => Some(LETTER_YEH),
+            '\u{06A9}' if self.persian => Some(LETTER_KAF),
+            '\u{06D2}' if self.urdu => Some(LETTER_YEH),
+            '\u{06C1}' | '\u{06BE}' if self.urdu => Some(LETTER_HEH),
+            '\u{06C3}' if self.urdu => Some(LETTER_HEH),
+            c => Some(c),
+        }
+    }
+
+    /// Fold every char of `text`.
+    pub fn normalize(&self, text: &str) -> String {
+        text.chars().filter_map(|c| self.fold(c)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_arabic_folding() {
+        let folding = ArabicFolding::default();
+        assert_eq!(folding.normalize(""), "");
+        assert_eq!(folding.normalize("بِسمِ اللَّهِ"), "بسم الله");
+        assert_eq!(folding.normalize("أإآٱا"), "ااااا");
+        assert_eq!(folding.normalize("رحمة"), "رحمه");
+        assert_eq!(folding.normalize("موسیٰ کتاب"), "موسي كتاب");
+        assert_eq!(folding.normalize("ہے ھ ۃ"), "هي ه ه");
+        assert_eq!(folding.normalize("يُؤمِنونَ سُئِلَ"), "يومنون سيل");
+        let folding = ArabicFolding {
+            alef: false,
+            urdu: false,
+            ..Default::default()
+        };
+        assert_eq!(folding.normalize("أَنتَ"), "أنت");
+        assert_eq!(folding.normalize("ہے"), "ہے");
+        let folding = ArabicFolding {
+            persian: false,
+            hamza: false,
+            ..Default::default()
+        };
+        assert_eq!(folding.normalize("موسیٰ کتاب"), "موسی کتاب");
+        assert_eq!(folding.normalize("ہے"), "هي");
+        assert_eq!(folding.normalize("يُؤمِنونَ سُئِلَ"), "يؤمنون سئل");
+    }
+}
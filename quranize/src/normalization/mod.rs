@@ -1,21 +1,159 @@
+mod arabic;
+
+pub use arabic::ArabicFolding;
+
+use crate::transliteration::chars::is_arabic_letter;
+
+const COMBINING_MACRON: char = '\u{0304}';
+const COMBINING_DOT_BELOW: char = '\u{0323}';
+const COMBINING_DOT_ABOVE: char = '\u{0307}';
+const COMBINING_CARON: char = '\u{030C}';
+const COMBINING_BREVE_BELOW: char = '\u{032E}';
+const COMBINING_MACRON_BELOW: char = '\u{0331}';
+
 pub(super) fn normalize(text: &str) -> String {
-    text.chars()
-        .filter_map(|c| match c.to_ascii_lowercase() {
-            c @ ('a'..='z' | '\'') => Some(c),
-            _ => None,
+    normalize_with(text, false)
+}
+
+/// Like [`normalize`], but keeping the digits of Arabizi, e.g. `7` for ح or `3` for ع.
+pub(super) fn normalize_arabizi(text: &str) -> String {
+    normalize_with(text, true)
+}
+
+/// Like [`normalize`], but reading uppercase `S`, `D`, `T`, `Z` and `H` as emphatic letters.
+pub(super) fn normalize_case_sensitive(text: &str) -> String {
+    normalize(&emphatics(text))
+}
+
+/// Like [`normalize_case_sensitive`], but keeping the digits of Arabizi.
+pub(super) fn normalize_case_sensitive_arabizi(text: &str) -> String {
+    normalize_arabizi(&emphatics(text))
+}
+
+fn normalize_with(text: &str, arabizi: bool) -> String {
+    { academic_chars(text).into_iter() }
+        .filter_map(|c| match c {
+            'a'..='z' | '\'' => Some(c),
+            'ā' | 'ī' | 'ū' | 'ḥ' | 'ṣ' | 'ḍ' | 'ṭ' | 'ẓ' | 'ʿ' | 'ʾ' => Some(c),
+            '2' | '3' | '5' | '6' | '7' | '9' if arabizi => Some(c),
+            c => fold_arabic(c).filter(|&c| is_arabic_letter(c)),
+        })
+        .collect()
+}
+
+fn emphatics(text: &str) -> String {
+    { text.chars() }
+        .map(|c| match c {
+            'S' => 'ṣ',
+            'D' => 'ḍ',
+            'T' => 'ṭ',
+            'Z' => 'ẓ',
+            'H' => 'ḥ',
+            c => c,
         })
         .collect()
 }
 
 pub(super) fn normalize_muqottoah(text: &str) -> String {
-    let mut chars = Vec::from_iter(text.chars().filter_map(|c| match c.to_ascii_lowercase() {
-        c @ ('a'..='z' | '\'' | ' ') => Some(c),
-        _ => None,
-    }));
+    let chars = academic_chars(text).into_iter().map(to_ascii);
+    let mut chars = Vec::from_iter(chars.filter(|c| matches!(c, 'a'..='z' | '\'' | ' ')));
     chars.dedup_by(|&mut a, &mut b| a == b && (a == 'a' || a == 'o' || a == 'i' || a == 'u'));
     chars.into_iter().filter(|&c| c != ' ').collect()
 }
 
+/// Lowercase chars of `text`, with the letters of academic transliteration composed,
+/// whether they are written precomposed (e.g. `ḥ`) or decomposed (e.g. `h` + U+0323).
+/// The letters having a common digraph, e.g. `ḏ` or `š`, are spelled with it instead.
+fn academic_chars(text: &str) -> Vec<char> {
+    let mut chars: Vec<char> = Vec::with_capacity(text.len());
+    for c in text.chars() {
+        let composed = match (chars.last(), c) {
+            (Some('a'), COMBINING_MACRON) => 'ā',
+            (Some('i'), COMBINING_MACRON) => 'ī',
+            (Some('u'), COMBINING_MACRON) => 'ū',
+            (Some('h'), COMBINING_DOT_BELOW) => 'ḥ',
+            (Some('s'), COMBINING_DOT_BELOW) => 'ṣ',
+            (Some('d'), COMBINING_DOT_BELOW) => 'ḍ',
+            (Some('t'), COMBINING_DOT_BELOW) => 'ṭ',
+            (Some('z'), COMBINING_DOT_BELOW) => 'ẓ',
+            (Some('d'), COMBINING_MACRON_BELOW) => 'ḏ',
+            (Some('t'), COMBINING_MACRON_BELOW) => 'ṯ',
+            (Some('s'), COMBINING_CARON) => 'š',
+            (Some('g'), COMBINING_CARON) => 'ǧ',
+            (Some('j'), COMBINING_CARON) => 'ǰ',
+            (Some('g'), COMBINING_DOT_ABOVE) => 'ġ',
+            (Some('h'), COMBINING_BREVE_BELOW) => 'ḫ',
+            _ => {
+                chars.push(match c {
+                    'Ā' => 'ā',
+                    'Ī' => 'ī',
+                    'Ū' => 'ū',
+                    'Ḥ' => 'ḥ',
+                    'Ṣ' => 'ṣ',
+                    'Ḍ' => 'ḍ',
+                    'Ṭ' => 'ṭ',
+                    'Ẓ' => 'ẓ',
+                    'Ḏ' => 'ḏ',
+                    'Ṯ' => 'ṯ',
+                    'Š' => 'š',
+                    'Ǧ' => 'ǧ',
+                    'Ġ' => 'ġ',
+                    'Ḫ' => 'ḫ',
+                    c => c.to_ascii_lowercase(),
+                });
+                continue;
+            }
+        };
+        chars.pop();
+        chars.push(composed);
+    }
+    let mut spelled = Vec::with_capacity(chars.len());
+    for c in chars {
+        match digraph(c) {
+            Some(digraph) => spelled.extend(digraph.chars()),
+            None => spelled.push(c),
+        }
+    }
+    spelled
+}
+
+fn digraph(c: char) -> Option<&'static str> {
+    match c {
+        'ḏ' => Some("dz"),
+        'ṯ' => Some("ts"),
+        'š' => Some("sy"),
+        'ǧ' | 'ǰ' => Some("j"),
+        'ġ' => Some("gh"),
+        'ḫ' => Some("kh"),
+        _ => None,
+    }
+}
+
+fn to_ascii(c: char) -> char {
+    match c {
+        'ā' => 'a',
+        'ī' => 'i',
+        'ū' => 'u',
+        'ḥ' => 'h',
+        'ṣ' => 's',
+        'ḍ' => 'd',
+        'ṭ' => 't',
+        'ẓ' => 'z',
+        'ʿ' | 'ʾ' => '\'',
+        c => c,
+    }
+}
+
+pub(super) fn normalize_arabic(text: &str) -> String {
+    { text.chars().filter_map(fold_arabic) }
+        .filter(|&c| is_arabic_letter(c))
+        .collect()
+}
+
+pub(super) fn fold_arabic(c: char) -> Option<char> {
+    ArabicFolding::default().fold(c)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -31,6 +169,56 @@ mod tests {
             "bismillahirrohmanirrohiim",
             normalize("bismilla hirrohma nirrohiim"),
         );
+        assert_eq!("قلhuwallahu", normalize("قُل Huwallahu"));
+    }
+
+    #[test]
+    fn test_normalize_academic() {
+        assert_eq!(
+            "alḥamdulillāhirabbilʿālamīn",
+            normalize("al-Ḥamdu lillāhi rabbi l-ʿālamīn")
+        );
+        assert_eq!(
+            "ḥṣḍṭẓāīū",
+            normalize("h\u{0323}s\u{0323}d\u{0323}t\u{0323}z\u{0323}a\u{0304}i\u{0304}u\u{0304}")
+        );
+        assert_eq!("saʾala", normalize("saʾala"));
+        assert_eq!("dzālika", normalize("ḏālika"));
+        assert_eq!("dzālika", normalize("d\u{0331}ālika"));
+        assert_eq!("tsalātsa", normalize("Ṯalāṯa"));
+        assert_eq!("syayʾ", normalize("šayʾ"));
+        assert_eq!("syayʾ", normalize("s\u{030C}ayʾ"));
+        assert_eq!("jannah", normalize("ǧannah"));
+        assert_eq!("ghafūr", normalize("ġafūr"));
+        assert_eq!("khayr", normalize("ḫayr"));
+        assert_eq!("khayr", normalize("h\u{032E}ayr"));
+        assert_eq!("alifla'mim", normalize_muqottoah("alif lāʾ mīm"));
+        assert_eq!("kafhayaainsad", normalize_muqottoah("kāf hā yā ain ṣād"));
+    }
+
+    #[test]
+    fn test_normalize_case_sensitive() {
+        assert_eq!("ṣirāṭ", normalize_case_sensitive("SirāT"));
+        assert_eq!("alḥamdu", normalize_case_sensitive("AlHamdu"));
+        assert_eq!("waḍḍuḥa", normalize_case_sensitive("wa DDuHa"));
+    }
+
+    #[test]
+    fn test_normalize_arabizi() {
+        assert_eq!("al7amdulillah", normalize_arabizi("Al7amdu lillah"));
+        assert_eq!("ya2ayyuha", normalize_arabizi("ya 2ayyuha 1:4"));
+        assert_eq!("235679", normalize_arabizi("0123456789"));
+        assert_eq!("9iroṭ", normalize_case_sensitive_arabizi("9iroT"));
+        assert_eq!("alamdulillah", normalize("Al7amdu lillah"));
+        assert_eq!("", normalize("0123456789"));
+    }
+
+    #[test]
+    fn test_normalize_arabic() {
+        assert_eq!("", normalize_arabic(""));
+        assert_eq!("", normalize_arabic("bismi"));
+        assert_eq!("قلهوالله", normalize_arabic("قُل هُوَ اللَّهُ"));
+        assert_eq!("اليربك", normalize_arabic("إِلىٰ رَبِّكَ ۚ"));
     }
 
     #[test]
@@ -0,0 +1,161 @@
+use crate::{Index, Quranize};
+
+#[cfg(feature = "regex")]
+use crate::{transliteration::chars::is_arabic_letter, ArabicFolding};
+
+impl Quranize {
+    /// Find `pattern` in Alquran like [`Quranize::find`], where `*` stands for any text in the aya,
+    /// e.g. the words between the start and the end of a partially remembered phrase.
+    /// The text before the first `*` is found through the suffix tree,
+    /// and the rest of `pattern` is checked within the ayas found.
+    ///
+    /// # Examples
+    /// ```
+    /// let q = quranize::Quranize::new();
+    /// let locations = q.find_wildcard("بِسمِ * الرَّحيمِ");
+    /// assert_eq!(locations[0], (0, 0));
+    /// assert_eq!(locations.len(), 2);
+    /// assert_eq!(q.find_wildcard("قُل * الفَلَقِ"), [(6225, 0)]);
+    /// ```
+    pub fn find_wildcard(&self, pattern: &str) -> Vec<Index> {
+        let pattern = pattern.trim_matches(|c: char| c == '*' || c.is_whitespace());
+        let mut segments = pattern.split('*').filter(|s| !s.is_empty());
+        let Some(prefix) = segments.next() else {
+            return vec![];
+        };
+        let segments = Vec::from_iter(segments);
+        let mut locations = self.find(prefix);
+        locations.retain(|&(i, j)| {
+            let mut rest = &self.saqs[i].2[j + prefix.len()..];
+            segments.iter().all(|s| match rest.find(s) {
+                Some(k) => {
+                    rest = &rest[k + s.len()..];
+                    true
+                }
+                None => false,
+            })
+        });
+        locations
+    }
+
+    /// Find the matches of regular expression `pattern` over Alquran text without harakat and other marks,
+    /// with orthographic variants folded as in [`Quranize::find_rasm`] on both `pattern` and quran text.
+    /// Like [`Quranize::find`], matches start at the start of a word, and their locations are
+    /// offsets in the fully diacritised aya.
+    /// The literal text `pattern` starts with is found through the suffix tree,
+    /// and the rest of `pattern` is checked within the ayas found.
+    ///
+    /// Requires feature `regex`.
+    ///
+    /// # Examples
+    /// ```
+    /// let q = quranize::Quranize::new();
+    /// let locations = q.find_regex(r"قل (اعوذ|هو) \S+ (الفلق|الناس|احد)").unwrap();
+    /// assert_eq!(locations, [(6221, 0), (6225, 0), (6230, 0)]);
+    /// assert_eq!(q.find_regex("عم يتساءلو(ن|ا)").unwrap(), [(5672, 0)]);
+    /// assert!(q.find_regex("(عم").is_err());
+    /// ```
+    #[cfg(feature = "regex")]
+    pub fn find_regex(&self, pattern: &str) -> Result<Vec<Index>, regex::Error> {
+        let folding = ArabicFolding::default();
+        let pattern = folding.normalize(pattern);
+        let regex = regex::Regex::new(&pattern)?;
+        let mut candidates = match literal_prefix(&pattern) {
+            "" => { self.saqs.iter().enumerate() }
+                .filter(|(_, (_, _, aya))| regex.is_match(&folding.normalize(aya)))
+                .flat_map(|(i, (_, _, aya))| Self::words(aya).map(move |(j, _)| (i, j)))
+                .collect(),
+            prefix => Vec::from_iter(self.find_rasm(prefix).into_iter().map(|(i, j, _)| (i, j))),
+        };
+        candidates.dedup();
+        let mut locations = vec![];
+        for rows in candidates.chunk_by(|(i, _), (k, _)| i == k) {
+            let (i, aya) = (rows[0].0, self.saqs[rows[0].0].2);
+            let chars = Vec::from_iter(
+                aya.char_indices()
+                    .filter_map(|(j, c)| Some((j, folding.fold(c)?))),
+            );
+            let text = String::from_iter(chars.iter().map(|&(_, c)| c));
+            let offsets = Vec::from_iter(text.char_indices().map(|(x, _)| x));
+            for &(_, j) in rows {
+                let x = chars.partition_point(|&(k, _)| k < j);
+                let Some(&start) = offsets.get(x) else {
+                    continue;
+                };
+                if { regex.find_at(&text, start) }
+                    .is_some_and(|m| m.start() == start && !m.is_empty())
+                {
+                    locations.push((i, j));
+                }
+            }
+        }
+        Ok(locations)
+    }
+}
+
+/// Literal text every match of regular expression `pattern` starts with, made of Arabic letters and spaces.
+#[cfg(feature = "regex")]
+fn literal_prefix(pattern: &str) -> &str {
+    let (mut depth, mut chars) = (0, pattern.chars());
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => _ = chars.next(),
+            '[' => _ = chars.by_ref().find(|&c| c == ']'),
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            '|' if depth == 0 => return "",
+            _ => (),
+        }
+    }
+    let end = { pattern.char_indices() }
+        .find(|&(_, c)| !is_arabic_letter(c) && c != ' ')
+        .map_or(pattern.len(), |(j, _)| j);
+    let prefix = &pattern[..end];
+    let prefix = match pattern[end..].starts_with(['*', '?', '{']) {
+        true => prefix
+            .char_indices()
+            .last()
+            .map_or("", |(j, _)| &prefix[..j]),
+        false => prefix,
+    };
+    prefix.trim_end()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_find_wildcard() {
+        let q = Quranize::new();
+        assert_eq!(q.find_wildcard("قُل أَعوذُ"), q.find("قُل أَعوذُ"));
+        assert_eq!(q.find_wildcard("* قُل أَعوذُ *"), q.find("قُل أَعوذُ"));
+        assert_eq!(q.find_wildcard("قُل أَعوذُ * النّاسِ"), [(6230, 0)]);
+        assert_eq!(q.find_wildcard("قُل أَعوذُ * النّاسِ * النّاسِ"), []);
+        assert_eq!(q.find_wildcard("عَمَّ*ونَ"), [(5672, 0)]);
+        assert_eq!(q.find_wildcard("**"), []);
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn test_find_regex() {
+        let q = Quranize::new();
+        assert_eq!(q.find_regex(r"^\S+ يتساءلون").unwrap(), [(5672, 0)]);
+        let locations = q.find_rasm("يتساءلون").into_iter().map(|(i, j, _)| (i, j));
+        assert_eq!(q.find_regex("يتساءلون").unwrap(), Vec::from_iter(locations));
+        assert_eq!(q.find_regex("ساءلون").unwrap(), []);
+        assert_eq!(q.find_regex("").unwrap(), []);
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn test_literal_prefix() {
+        assert_eq!(literal_prefix("قل هو"), "قل هو");
+        assert_eq!(literal_prefix("قل هو.+"), "قل هو");
+        assert_eq!(literal_prefix("قل هو*"), "قل ه");
+        assert_eq!(literal_prefix("قل (هو|اعوذ)"), "قل");
+        assert_eq!(literal_prefix("قل|هو"), "");
+        assert_eq!(literal_prefix("^قل"), "");
+    }
+}
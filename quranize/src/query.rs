@@ -0,0 +1,387 @@
+use crate::{EncodeOptions, FindOptions, Match, Quranize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Error returned when a query of [`Quranize::query`] is not valid.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum QueryError {
+    /// The query ends where a term is expected.
+    UnexpectedEnd,
+    /// The query has an operator or a parenthesis where it is not expected.
+    UnexpectedToken(String),
+    /// A quote or a parenthesis is not closed.
+    Unclosed(char),
+    /// The distance of a `NEAR/n` operator is not a number.
+    InvalidDistance(String),
+    /// A `NOT` is not excluding ayas from the other operand of an `AND`.
+    BareNot,
+}
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnexpectedEnd => write!(f, "unexpected end of query"),
+            Self::UnexpectedToken(t) => write!(f, "unexpected {:?} in query", t),
+            Self::Unclosed(c) => write!(f, "unclosed {:?} in query", c),
+            Self::InvalidDistance(t) => write!(f, "invalid distance in {:?}", t),
+            Self::BareNot => write!(f, "NOT must be an operand of AND, as in \"a AND NOT b\""),
+        }
+    }
+}
+
+impl std::error::Error for QueryError {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Token {
+    Open,
+    Close,
+    And,
+    Or,
+    Not,
+    Near(usize),
+    Term(String),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Expr {
+    Term(String),
+    Not(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Near(Box<Expr>, Box<Expr>, usize),
+}
+
+/// Matches of an expression, by aya row.
+type Hits = BTreeMap<usize, Vec<Match>>;
+
+impl Quranize {
+    /// Find ayas satisfying `query`, a Boolean combination of terms with operators
+    /// `AND`, `OR`, `NOT` and `NEAR/n`, from the tightest: `NOT`, `NEAR/n`, `AND`, `OR`,
+    /// where parentheses group sub-queries.
+    /// `NOT` only excludes ayas from the other operand of an `AND`, as in `a AND NOT b`.
+    /// `a NEAR/n b` requires a match of `a` and a match of `b` with at most `n` words between them.
+    ///
+    /// A term is a phrase of consecutive words, or a quoted phrase, matching whole words.
+    /// Arabic terms are found as in [`Quranize::find_rasm`],
+    /// and other terms are transliterated as in [`Quranize::encode`].
+    /// Returns a list of tuple, in mushaf order:
+    /// - `usize`: aya row / aya offset (`0..6236`)
+    /// - `Vec<Match>`: matches of the terms in the aya, except the negated ones
+    ///
+    /// # Examples
+    /// ```
+    /// let q = quranize::Quranize::new();
+    /// let ayas = q.query("الصلاة AND الزكاة AND NOT الحج").unwrap();
+    /// assert!(ayas.iter().all(|(i, _)| !q.get_quran(*i).unwrap().contains("الحَجّ")));
+    /// let ayas = q.query("robbi NEAR/0 ighfir").unwrap();
+    /// assert!(ayas.iter().any(|(i, _)| q.get_quran(*i).unwrap().contains("رَبِّ اغفِر")));
+    /// assert!(q.query("(qul OR").is_err());
+    /// assert!(q.query("NOT qul").is_err());
+    /// ```
+    pub fn query(&self, query: &str) -> Result<Vec<(usize, Vec<Match>)>, QueryError> {
+        let tokens = tokenize(query)?;
+        let (expr, rest) = parse_or(&tokens)?;
+        if let Some(t) = rest.first() {
+            return Err(QueryError::UnexpectedToken(t.to_string()));
+        }
+        Ok(Vec::from_iter(self.eval(&expr)?))
+    }
+
+    fn eval(&self, expr: &Expr) -> Result<Hits, QueryError> {
+        Ok(match expr {
+            Expr::Term(term) => self.eval_term(term),
+            Expr::Not(_) => return Err(QueryError::BareNot),
+            Expr::And(x, y) => match (x.as_ref(), y.as_ref()) {
+                (Expr::Not(_), Expr::Not(_)) => return Err(QueryError::BareNot),
+                (x, Expr::Not(y)) | (Expr::Not(y), x) => {
+                    let mut hits = self.eval(x)?;
+                    for i in self.eval(y)?.into_keys() {
+                        hits.remove(&i);
+                    }
+                    hits
+                }
+                (x, y) => {
+                    let mut hits = self.eval(y)?;
+                    { self.eval(x)?.into_iter() }
+                        .filter_map(|(i, mut ms)| {
+                            ms.extend(hits.remove(&i)?);
+                            Some((i, ms))
+                        })
+                        .collect()
+                }
+            },
+            Expr::Or(x, y) => {
+                let mut hits = self.eval(x)?;
+                for (i, ms) in self.eval(y)? {
+                    hits.entry(i).or_default().extend(ms);
+                }
+                hits
+            }
+            Expr::Near(x, y, n) => {
+                let mut hits = self.eval(y)?;
+                { self.eval(x)?.into_iter() }
+                    .filter_map(|(i, xs)| {
+                        let ys = hits.remove(&i)?;
+                        let is_near = |a: &Match, b: &Match| words_between(a, b) <= *n;
+                        let xs =
+                            Vec::from_iter(xs.iter().filter(|a| ys.iter().any(|b| is_near(a, b))));
+                        let ys = ys.iter().filter(|b| xs.iter().any(|a| is_near(a, b)));
+                        let ms = Vec::from_iter(xs.iter().copied().chain(ys).copied());
+                        (!ms.is_empty()).then_some((i, ms))
+                    })
+                    .collect()
+            }
+        }
+        .into_iter()
+        .map(|(i, mut ms)| {
+            ms.sort();
+            ms.dedup();
+            (i, ms)
+        })
+        .collect())
+    }
+
+    fn eval_term(&self, term: &str) -> Hits {
+        let whole_word = FindOptions {
+            whole_word: true,
+            ..Default::default()
+        };
+        let matches: Vec<Match> = match Self::is_rasm(term) {
+            true => { self.find_rasm_with(term, &whole_word).into_iter() }
+                .filter_map(|(i, j, k)| self.get_match(i, j, k))
+                .collect(),
+            false => {
+                let options = EncodeOptions {
+                    find: whole_word.clone(),
+                    ..Default::default()
+                };
+                { self.encode_found(term, &options).into_iter() }
+                    .flat_map(|(q, found, _)| {
+                        { self.collect_found(&found, &whole_word).into_iter() }
+                            .filter_map(|(i, j)| self.get_match(i, j, j + q.len()))
+                            .collect::<Vec<_>>()
+                    })
+                    .collect()
+            }
+        };
+        let mut hits = Hits::new();
+        for m in matches {
+            hits.entry(m.row).or_default().push(m);
+        }
+        hits
+    }
+}
+
+/// Number of words between matches `a` and `b` of an aya, or 0 when they overlap.
+fn words_between(a: &Match, b: &Match) -> usize {
+    match (a.word_end <= b.word_start, b.word_end <= a.word_start) {
+        (true, _) => b.word_start - a.word_end,
+        (_, true) => a.word_start - b.word_end,
+        _ => 0,
+    }
+}
+
+fn tokenize(query: &str) -> Result<Vec<Token>, QueryError> {
+    let mut tokens = vec![];
+    let mut chars = query.chars().peekable();
+    while let Some(c) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '(' => Token::Open,
+            ')' => Token::Close,
+            '"' => {
+                let mut term = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some(c) => term.push(c),
+                        None => return Err(QueryError::Unclosed('"')),
+                    }
+                }
+                Token::Term(term)
+            }
+            c => {
+                let mut word = c.to_string();
+                while let Some(&c) = chars
+                    .peek()
+                    .filter(|c| !c.is_whitespace() && !"()\"".contains(**c))
+                {
+                    word.push(c);
+                    chars.next();
+                }
+                match word.as_str() {
+                    "AND" => Token::And,
+                    "OR" => Token::Or,
+                    "NOT" => Token::Not,
+                    w => match w.strip_prefix("NEAR/") {
+                        Some(n) => Token::Near(
+                            n.parse()
+                                .map_err(|_| QueryError::InvalidDistance(word.clone()))?,
+                        ),
+                        None => Token::Term(word),
+                    },
+                }
+            }
+        };
+        match (tokens.last_mut(), token) {
+            (Some(Token::Term(phrase)), Token::Term(word)) => {
+                phrase.push(' ');
+                phrase.push_str(&word);
+            }
+            (_, token) => tokens.push(token),
+        }
+    }
+    Ok(tokens)
+}
+
+fn parse_or(tokens: &[Token]) -> Result<(Expr, &[Token]), QueryError> {
+    let (mut expr, mut tokens) = parse_and(tokens)?;
+    while let Some((Token::Or, rest)) = tokens.split_first() {
+        let (y, rest) = parse_and(rest)?;
+        (expr, tokens) = (Expr::Or(Box::new(expr), Box::new(y)), rest);
+    }
+    Ok((expr, tokens))
+}
+
+fn parse_and(tokens: &[Token]) -> Result<(Expr, &[Token]), QueryError> {
+    let (mut expr, mut tokens) = parse_near(tokens)?;
+    while let Some((Token::And, rest)) = tokens.split_first() {
+        let (y, rest) = parse_near(rest)?;
+        (expr, tokens) = (Expr::And(Box::new(expr), Box::new(y)), rest);
+    }
+    Ok((expr, tokens))
+}
+
+fn parse_near(tokens: &[Token]) -> Result<(Expr, &[Token]), QueryError> {
+    let (mut expr, mut tokens) = parse_unary(tokens)?;
+    while let Some((&Token::Near(n), rest)) = tokens.split_first() {
+        let (y, rest) = parse_unary(rest)?;
+        (expr, tokens) = (Expr::Near(Box::new(expr), Box::new(y), n), rest);
+    }
+    Ok((expr, tokens))
+}
+
+fn parse_unary(tokens: &[Token]) -> Result<(Expr, &[Token]), QueryError> {
+    match tokens.split_first() {
+        None => Err(QueryError::UnexpectedEnd),
+        Some((Token::Term(t), rest)) => Ok((Expr::Term(t.clone()), rest)),
+        Some((Token::Not, rest)) => {
+            let (x, rest) = parse_unary(rest)?;
+            Ok((Expr::Not(Box::new(x)), rest))
+        }
+        Some((Token::Open, rest)) => match parse_or(rest)? {
+            (x, [Token::Close, rest @ ..]) => Ok((x, rest)),
+            _ => Err(QueryError::Unclosed('(')),
+        },
+        Some((t, _)) => Err(QueryError::UnexpectedToken(t.to_string())),
+    }
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Open => write!(f, "("),
+            Self::Close => write!(f, ")"),
+            Self::And => write!(f, "AND"),
+            Self::Or => write!(f, "OR"),
+            Self::Not => write!(f, "NOT"),
+            Self::Near(n) => write!(f, "NEAR/{}", n),
+            Self::Term(t) => write!(f, "{}", t),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn parse(query: &str) -> Result<Expr, QueryError> {
+        let tokens = tokenize(query)?;
+        match parse_or(&tokens)? {
+            (expr, []) => Ok(expr),
+            (_, [t, ..]) => Err(QueryError::UnexpectedToken(t.to_string())),
+        }
+    }
+
+    fn term(t: &str) -> Box<Expr> {
+        Box::new(Expr::Term(t.to_string()))
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse("a b"), Ok(Expr::Term("a b".to_string())));
+        assert_eq!(parse("\"a  b\" c"), Ok(Expr::Term("a  b c".to_string())));
+        assert_eq!(
+            parse("a OR b AND NOT c"),
+            Ok(Expr::Or(
+                term("a"),
+                Box::new(Expr::And(term("b"), Box::new(Expr::Not(term("c")))))
+            ))
+        );
+        assert_eq!(
+            parse("(a OR b) NEAR/3 c"),
+            Ok(Expr::Near(
+                Box::new(Expr::Or(term("a"), term("b"))),
+                term("c"),
+                3
+            ))
+        );
+        assert_eq!(parse(""), Err(QueryError::UnexpectedEnd));
+        assert_eq!(parse("a AND"), Err(QueryError::UnexpectedEnd));
+        assert_eq!(parse("(a"), Err(QueryError::Unclosed('(')));
+        assert_eq!(parse("\"a"), Err(QueryError::Unclosed('"')));
+        assert_eq!(
+            parse("a)"),
+            Err(QueryError::UnexpectedToken(")".to_string()))
+        );
+        assert_eq!(
+            parse("OR a"),
+            Err(QueryError::UnexpectedToken("OR".to_string()))
+        );
+        assert_eq!(
+            parse("a NEAR/x b"),
+            Err(QueryError::InvalidDistance("NEAR/x".to_string()))
+        );
+        assert_eq!(
+            QueryError::InvalidDistance("NEAR/x".to_string()).to_string(),
+            "invalid distance in \"NEAR/x\""
+        );
+    }
+
+    #[test]
+    fn test_query() {
+        let q = Quranize::new();
+        let rows = |query| Vec::from_iter(q.query(query).unwrap().into_iter().map(|(i, _)| i));
+        let salat = rows("الصلاة");
+        let zakat = rows("الزكاة");
+        let hajj = rows("الحج");
+        let and = rows("الصلاة AND الزكاة");
+        assert!(!and.is_empty());
+        assert!(and.iter().all(|i| salat.contains(i) && zakat.contains(i)));
+        let and_not = rows("الصلاة AND الزكاة AND NOT الحج");
+        assert!(and_not.iter().all(|i| and.contains(i) && !hajj.contains(i)));
+        assert_eq!(
+            rows("الصلاة OR الزكاة").len(),
+            salat.len() + zakat.len() - and.len()
+        );
+        assert_eq!(rows("NOT الحج AND الصلاة AND الزكاة"), and_not);
+        assert_eq!(q.query("NOT الصلاة"), Err(QueryError::BareNot));
+        assert_eq!(q.query("الصلاة OR NOT الزكاة"), Err(QueryError::BareNot));
+        assert_eq!(
+            q.query("NOT الصلاة AND NOT الزكاة"),
+            Err(QueryError::BareNot)
+        );
+        let near = rows("رَبِّ NEAR/3 اغفِر");
+        assert!(near.iter().all(|i| rows("رَبِّ AND اغفِر").contains(i)));
+        assert!(rows("رَبِّ NEAR/0 اغفِر").iter().all(|i| near.contains(i)));
+        assert!(rows("اغفِر NEAR/3 رَبِّ") == near);
+        let latin = rows("ashsholata AND azzakata");
+        assert!(!latin.is_empty());
+        assert!(latin.iter().all(|i| and.contains(i)));
+        let (i, ms) = &q.query("qul huwallahu NEAR/1 ahad").unwrap()[0];
+        let aya = q.get_quran(*i).unwrap();
+        let texts = Vec::from_iter(ms.iter().map(|m| &aya[m.start..m.end]));
+        assert_eq!(texts, ["قُل هُوَ اللَّهُ", "أَحَدٌ"]);
+    }
+}
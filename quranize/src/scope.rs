@@ -0,0 +1,101 @@
+use crate::{AYA_COUNT, JUZ_STARTS, SURA_STARTS};
+use std::ops::Range;
+
+/// Aya rows where text is found, e.g. the suras or the juz being memorized.
+/// The default scope is the whole Alquran.
+///
+/// # Examples
+///
+/// ```
+/// use quranize::Scope;
+/// assert!(Scope::suras(&[1, 114]).contains(0));
+/// assert!(!Scope::suras(&[1, 114]).contains(7));
+/// assert!(Scope::juz(30).contains(6235));
+/// assert!(Scope::rows(10..20).contains(19));
+/// assert!(Scope::default().contains(6235));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Scope {
+    ranges: Vec<Range<usize>>,
+}
+
+impl Default for Scope {
+    fn default() -> Self {
+        Self::rows(0..AYA_COUNT)
+    }
+}
+
+impl Scope {
+    /// Scope of aya rows / aya offsets in `rows`.
+    pub fn rows(rows: Range<usize>) -> Self {
+        Self::from_ranges(vec![rows])
+    }
+
+    /// Scope of the ayas of the suras numbered `suras`.
+    pub fn suras(suras: &[u8]) -> Self {
+        Self::from_ranges(Vec::from_iter(
+            { suras.iter().map(|&s| s as usize) }
+                .filter(|s| (1..=SURA_STARTS.len()).contains(s))
+                .map(|s| SURA_STARTS[s - 1]..SURA_STARTS.get(s).copied().unwrap_or(AYA_COUNT)),
+        ))
+    }
+
+    /// Scope of the ayas of juz number `juz`.
+    pub fn juz(juz: u8) -> Self {
+        let j = juz as usize;
+        Self::from_ranges(Vec::from_iter(
+            { (1..=JUZ_STARTS.len()).contains(&j).then_some(j) }
+                .map(|j| JUZ_STARTS[j - 1]..JUZ_STARTS.get(j).copied().unwrap_or(AYA_COUNT)),
+        ))
+    }
+
+    fn from_ranges(mut ranges: Vec<Range<usize>>) -> Self {
+        ranges.retain(|r| !r.is_empty());
+        ranges.sort_by_key(|r| r.start);
+        let mut merged: Vec<Range<usize>> = Vec::with_capacity(ranges.len());
+        for r in ranges {
+            match merged.last_mut() {
+                Some(last) if r.start <= last.end => last.end = last.end.max(r.end),
+                _ => merged.push(r),
+            }
+        }
+        Self { ranges: merged }
+    }
+
+    /// Whether aya row `i` is in this scope.
+    pub fn contains(&self, i: usize) -> bool {
+        self.ranges.iter().any(|r| r.contains(&i))
+    }
+
+    /// Whether some rows from `min` to `max` inclusive are in this scope.
+    pub(crate) fn overlaps(&self, (min, max): (usize, usize)) -> bool {
+        self.ranges.iter().any(|r| r.start <= max && min < r.end)
+    }
+
+    /// Whether all rows from `min` to `max` inclusive are in this scope.
+    pub(crate) fn covers(&self, (min, max): (usize, usize)) -> bool {
+        self.ranges.iter().any(|r| r.start <= min && max < r.end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_scope() {
+        assert_eq!(Scope::suras(&[114, 113, 112]), Scope::rows(6221..AYA_COUNT));
+        assert_eq!(Scope::suras(&[2, 0, 115]), Scope::rows(7..293));
+        assert_eq!(Scope::juz(1), Scope::rows(0..148));
+        assert_eq!(Scope::juz(31), Scope::suras(&[]));
+        assert!(!Scope::suras(&[]).contains(0));
+        let scope = Scope::suras(&[1, 3]);
+        assert!(scope.overlaps((5, 100)));
+        assert!(!scope.overlaps((7, 292)));
+        assert!(scope.covers((293, 300)));
+        assert!(!scope.covers((0, 293)));
+        assert!(Scope::default().covers((0, AYA_COUNT - 1)));
+        assert!(!Scope::default().overlaps((usize::MAX, 0)));
+    }
+}
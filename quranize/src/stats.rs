@@ -0,0 +1,256 @@
+//! Word statistics of Alquran, e.g. for a concordance or word-frequency research.
+//!
+//! Words are split the way suffixes are indexed for finding text:
+//! by spaces, waqf marks and the ends of ayas.
+//!
+//! # Examples
+//!
+//! ```
+//! let q = quranize::Quranize::new();
+//! let vocabulary = quranize::stats::vocabulary(&q);
+//! let word = vocabulary.iter().find(|w| w.word == "أَحَدٌ").unwrap();
+//! assert_eq!((word.rasm.as_str(), word.count, word.rasm_count), ("أحد", 13, 30));
+//! assert_eq!((word.first, word.suras.last()), ((365, 155), Some(&(112, 2))));
+//!
+//! let csv = quranize::stats::to_csv(&vocabulary[..1]);
+//! assert_eq!(csv.lines().next(), Some("word,rasm,count,rasm_count,first_row,first_offset,suras"));
+//! ```
+
+use crate::suffix_tree::{is_separator, suffix_iter, Index};
+use crate::transliteration::chars::is_arabic_letter;
+use crate::{ArabicFolding, Quranize};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// Statistics of a distinct word form of Alquran, as returned by [`vocabulary`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct WordStats<'a> {
+    /// Word form, with harakat and other marks.
+    pub word: &'a str,
+    /// Word form without harakat and other marks.
+    pub rasm: String,
+    /// Number of occurrences of the word form.
+    pub count: usize,
+    /// Number of occurrences of all word forms with the same [`WordStats::rasm`].
+    pub rasm_count: usize,
+    /// Location of the first occurrence, as aya row and string offset in the aya.
+    pub first: Index,
+    /// Number of occurrences per sura, as pairs of sura number and count, in mushaf order.
+    pub suras: Vec<(u8, usize)>,
+}
+
+/// Vocabulary of Alquran: statistics of every distinct word form,
+/// from the most frequent one, and in mushaf order of their first occurrences for equal counts.
+pub fn vocabulary(quranize: &Quranize) -> Vec<WordStats<'_>> {
+    let folding = ArabicFolding {
+        marks: true,
+        alef: false,
+        hamza: false,
+        alef_maksura: false,
+        teh_marbuta: false,
+        persian: false,
+        urdu: false,
+    };
+    let mut vocabulary: Vec<WordStats> = vec![];
+    let mut positions = HashMap::new();
+    for (i, &(sura, _, aya)) in quranize.saqs.iter().enumerate() {
+        for (j, suffix) in suffix_iter(aya) {
+            let word = suffix.split(is_separator).next().unwrap_or_default();
+            if !word.chars().any(is_arabic_letter) {
+                continue;
+            }
+            let k = *positions.entry(word).or_insert_with(|| {
+                vocabulary.push(WordStats {
+                    word,
+                    rasm: folding.normalize(word),
+                    count: 0,
+                    rasm_count: 0,
+                    first: (i, j),
+                    suras: vec![],
+                });
+                vocabulary.len() - 1
+            });
+            let stats = &mut vocabulary[k];
+            stats.count += 1;
+            match stats.suras.last_mut() {
+                Some((s, n)) if *s == sura => *n += 1,
+                _ => stats.suras.push((sura, 1)),
+            }
+        }
+    }
+    let mut rasm_counts = HashMap::new();
+    for stats in &vocabulary {
+        *rasm_counts.entry(stats.rasm.clone()).or_default() += stats.count;
+    }
+    for stats in &mut vocabulary {
+        stats.rasm_count = rasm_counts[&stats.rasm];
+    }
+    vocabulary.sort_by(|a, b| b.count.cmp(&a.count).then(a.first.cmp(&b.first)));
+    vocabulary
+}
+
+/// Export `vocabulary` as a JSON array of objects, with the fields of [`WordStats`],
+/// where `first` is an array of aya row and string offset,
+/// and `suras` is an array of arrays of sura number and count.
+/// With the `serde` feature, it is serialized by `serde_json` through [`WordStats`]'s `Serialize`.
+///
+/// # Examples
+/// ```
+/// let q = quranize::Quranize::new();
+/// let vocabulary = quranize::stats::vocabulary(&q);
+/// let word = vocabulary.iter().position(|w| w.word == "الفَلَقِ").unwrap();
+/// assert_eq!(
+///     quranize::stats::to_json(&vocabulary[word..word + 1]),
+///     r#"[{"word":"الفَلَقِ","rasm":"الفلق","count":1,"rasm_count":1,"first":[6225,35],"suras":[[113,1]]}]"#,
+/// );
+/// ```
+pub fn to_json(vocabulary: &[WordStats]) -> String {
+    #[cfg(feature = "serde")]
+    {
+        serde_json::to_string(vocabulary).expect("word stats are serializable")
+    }
+    #[cfg(not(feature = "serde"))]
+    {
+        write_json(vocabulary)
+    }
+}
+
+#[cfg(not(feature = "serde"))]
+fn write_json(vocabulary: &[WordStats]) -> String {
+    let mut json = String::from("[");
+    for (k, stats) in vocabulary.iter().enumerate() {
+        let suras = Vec::from_iter(stats.suras.iter().map(|(s, n)| format!("[{},{}]", s, n)));
+        _ = write!(
+            json,
+            r#"{}{{"word":"{}","rasm":"{}","count":{},"rasm_count":{},"first":[{},{}],"suras":[{}]}}"#,
+            if k == 0 { "" } else { "," },
+            escape_json(stats.word),
+            escape_json(&stats.rasm),
+            stats.count,
+            stats.rasm_count,
+            stats.first.0,
+            stats.first.1,
+            suras.join(","),
+        );
+    }
+    json.push(']');
+    json
+}
+
+/// Escape `s` as the content of a JSON string, as in RFC 8259.
+#[cfg(not(feature = "serde"))]
+fn escape_json(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            '\u{08}' => escaped.push_str("\\b"),
+            '\u{0C}' => escaped.push_str("\\f"),
+            c if c < ' ' => _ = write!(escaped, "\\u{:04x}", c as u32),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Export `vocabulary` as CSV with a header line, where `suras` is a space-separated list of
+/// `sura:count` pairs.
+/// Fields containing a comma, a double quote or a line break are quoted, as in RFC 4180.
+///
+/// # Examples
+/// ```
+/// let q = quranize::Quranize::new();
+/// let vocabulary = quranize::stats::vocabulary(&q);
+/// let word = vocabulary.iter().position(|w| w.word == "الفَلَقِ").unwrap();
+/// let csv = quranize::stats::to_csv(&vocabulary[word..word + 1]);
+/// assert_eq!(csv.lines().nth(1), Some("الفَلَقِ,الفلق,1,1,6225,35,113:1"));
+/// ```
+pub fn to_csv(vocabulary: &[WordStats]) -> String {
+    let mut csv = String::from("word,rasm,count,rasm_count,first_row,first_offset,suras\n");
+    for stats in vocabulary {
+        let suras = Vec::from_iter(stats.suras.iter().map(|(s, n)| format!("{}:{}", s, n)));
+        _ = writeln!(
+            csv,
+            "{},{},{},{},{},{},{}",
+            escape_csv(stats.word),
+            escape_csv(&stats.rasm),
+            stats.count,
+            stats.rasm_count,
+            stats.first.0,
+            stats.first.1,
+            suras.join(" "),
+        );
+    }
+    csv
+}
+
+/// Quote `field` when it contains a comma, a double quote or a line break, as in RFC 4180.
+fn escape_csv(field: &str) -> Cow<'_, str> {
+    match field.contains([',', '"', '\r', '\n']) {
+        true => Cow::Owned(format!("\"{}\"", field.replace('"', "\"\""))),
+        false => Cow::Borrowed(field),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FindOptions;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_vocabulary() {
+        let q = Quranize::new();
+        let vocabulary = vocabulary(&q);
+        let words = { q.saqs.iter() }
+            .flat_map(|(_, _, aya)| aya.split(is_separator))
+            .filter(|w| w.chars().any(is_arabic_letter));
+        assert_eq!(
+            vocabulary.iter().map(|w| w.count).sum::<usize>(),
+            words.count()
+        );
+        assert!(vocabulary.windows(2).all(|w| w[0].count >= w[1].count));
+        let whole_word = FindOptions {
+            whole_word: true,
+            ..Default::default()
+        };
+        for stats in &vocabulary[..20] {
+            assert_eq!(q.find_with(stats.word, &whole_word).len(), stats.count);
+            assert_eq!(q.find_with(stats.word, &whole_word)[0], stats.first);
+            assert_eq!(
+                stats.suras.iter().map(|(_, n)| n).sum::<usize>(),
+                stats.count
+            );
+            assert!(stats.rasm_count >= stats.count);
+        }
+        let csv = to_csv(&vocabulary);
+        assert_eq!(csv.lines().count(), vocabulary.len() + 1);
+        assert_eq!(to_json(&[]), "[]");
+    }
+
+    #[test]
+    fn test_escaping() {
+        let stats = [WordStats {
+            word: "a,\"b\"\\c\nd\u{01}",
+            rasm: "a\r\n".to_string(),
+            count: 1,
+            rasm_count: 2,
+            first: (3, 4),
+            suras: vec![(5, 1)],
+        }];
+        assert_eq!(
+            to_json(&stats),
+            r#"[{"word":"a,\"b\"\\c\nd\u0001","rasm":"a\r\n","count":1,"rasm_count":2,"first":[3,4],"suras":[[5,1]]}]"#,
+        );
+        assert_eq!(
+            to_csv(&stats).split_once('\n').unwrap().1,
+            "\"a,\"\"b\"\"\\c\nd\u{01}\",\"a\r\n\",1,2,3,4,5:1\n",
+        );
+    }
+}
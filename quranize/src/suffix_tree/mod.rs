@@ -1,10 +1,15 @@
+use crate::Scope;
 use std::collections::{btree_set::Range, BTreeSet};
 
 mod suffix_iter;
 
-type Vertex = (Option<Index>, usize, bool);
+pub(super) use suffix_iter::{is_separator, suffix_iter};
+
+/// Data, data count, whether it contains harf muqottoah, and the first and last rows of its data.
+type Vertex = (Option<Index>, usize, bool, (usize, usize));
 pub(super) type Index = (usize, usize);
 pub(super) type Edge<'a> = (usize, usize, &'a str);
+type Fold<'f> = &'f dyn Fn(char) -> Option<char>;
 
 pub(super) struct SuffixTree<'a> {
     pub(super) vertices: Vec<Vertex>,
@@ -14,12 +19,16 @@ pub(super) struct SuffixTree<'a> {
 impl<'a> SuffixTree<'a> {
     pub(super) fn with_capacity(capacity: usize) -> Self {
         let mut vertices = Vec::with_capacity(capacity);
-        vertices.push((None, 0, false));
+        vertices.push((None, 0, false, (usize::MAX, 0)));
         let edges = Default::default();
         Self { vertices, edges }
     }
 
+    /// Add the suffixes of `s` as the data of row `i`.
+    /// Rows must be added in increasing order, as the row ranges of the vertices rely on it.
     pub(super) fn construct(&mut self, i: usize, s: &'a str) {
+        let (_, max) = self.vertices[0].3;
+        assert!(max <= i, "row {} is added after row {}", i, max);
         suffix_iter::suffix_iter(s).for_each(|(j, s)| self.construct_suffix((i, j), 0, s));
     }
 
@@ -34,8 +43,9 @@ impl<'a> SuffixTree<'a> {
             }
             Some(((v, w, l), p)) => {
                 self.edges.remove(&(v, w, l));
-                let x = self.add_vertex((None, self.vertices[w].1 + 1, false));
-                let y = self.add_vertex((Some(i), 1, contains_harf_muqottoah(i)));
+                let (min, _) = self.vertices[w].3;
+                let x = self.add_vertex((None, self.vertices[w].1 + 1, false, (min, i.0)));
+                let y = self.add_vertex((Some(i), 1, contains_harf_muqottoah(i), (i.0, i.0)));
                 self.edges.insert((v, x, p));
                 self.edges.insert((x, w, &l[p.len()..]));
                 self.edges.insert((x, y, &s[p.len()..]));
@@ -43,15 +53,17 @@ impl<'a> SuffixTree<'a> {
                 self.vertices[v].2 |= self.vertices[x].2;
             }
             None => {
-                let w = self.add_vertex((Some(i), 1, contains_harf_muqottoah(i)));
+                let w = self.add_vertex((Some(i), 1, contains_harf_muqottoah(i), (i.0, i.0)));
                 self.edges.insert((v, w, s));
                 self.vertices[v].2 |= self.vertices[w].2;
             }
         }
         self.vertices[v].1 += 1;
+        let (min, _) = self.vertices[v].3;
+        self.vertices[v].3 = (min.min(i.0), i.0);
     }
 
-    pub(super) fn edges_from(&self, v: usize) -> Range<Edge<'a>> {
+    pub(super) fn edges_from(&self, v: usize) -> Range<'_, Edge<'a>> {
         self.edges.range((v, 0, "")..(v + 1, 0, ""))
     }
 
@@ -70,16 +82,95 @@ impl<'a> SuffixTree<'a> {
         self.vertices[v].1
     }
 
-    pub(super) fn find(&self, s: &str, v: usize) -> Vec<Index> {
+    /// Whether some data below `v` is in `scope`.
+    pub(super) fn overlaps(&self, v: usize, scope: &Scope) -> bool {
+        scope.overlaps(self.vertices[v].3)
+    }
+
+    /// Count the data below `v` in `scope`, skipping the subtrees outside `scope`.
+    pub(super) fn count_data_in(&self, v: usize, scope: &Scope) -> usize {
+        match self.vertices[v].3 {
+            rows if scope.covers(rows) => self.count_data(v),
+            rows if !scope.overlaps(rows) => 0,
+            _ => {
+                let data = self.vertices[v].0.filter(|&(i, _)| scope.contains(i));
+                let edges = self.edges_from(v);
+                data.into_iter().count()
+                    + edges
+                        .map(|&(_, w, _)| self.count_data_in(w, scope))
+                        .sum::<usize>()
+            }
+        }
+    }
+
+    pub(super) fn find(&self, s: &str, v: usize, scope: &Scope) -> Vec<Index> {
         self.edges_from(v)
+            .filter(|&&(_, w, _)| self.overlaps(w, scope))
             .flat_map(|&(_, w, l)| match (s.strip_prefix(l), l.strip_prefix(s)) {
-                (_, Some(_)) if !s.is_empty() => self.collect_data(w),
-                (Some(s), _) => self.find(s, w),
+                (_, Some(_)) if !s.is_empty() => self.collect_data_in(w, scope),
+                (Some(s), _) => self.find(s, w, scope),
                 _ => vec![],
             })
             .collect()
     }
 
+    /// Vertex below `v` whose data are the locations of `s`, found without collecting them.
+    pub(super) fn locate(&self, s: &str, v: usize) -> Option<usize> {
+        self.edges_from(v)
+            .find_map(|&(_, w, l)| match (s.strip_prefix(l), l.strip_prefix(s)) {
+                (_, Some(_)) if !s.is_empty() => Some(w),
+                (Some(s), _) => self.locate(s, w),
+                _ => None,
+            })
+    }
+
+    /// Find `s` like [`Self::find`], but comparing `s` with edge label chars folded by `fold`,
+    /// and ignoring label chars folded into `None`.
+    /// Returns each location together with the byte length of the matched label chars,
+    /// including the ignored chars right after the last matched char.
+    pub(super) fn find_folded(&self, s: &str, fold: Fold, scope: &Scope) -> Vec<(Index, usize)> {
+        match s {
+            "" => vec![],
+            s => { self.edges_from(0) }
+                .flat_map(|&e| self.find_folded_edge(s, e, 0, fold, scope))
+                .collect(),
+        }
+    }
+
+    fn find_folded_edge(
+        &self,
+        s: &str,
+        (_, w, l): Edge,
+        len: usize,
+        fold: Fold,
+        scope: &Scope,
+    ) -> Vec<(Index, usize)> {
+        if !self.overlaps(w, scope) {
+            return vec![];
+        }
+        let mut s = s;
+        for (i, c) in l.char_indices() {
+            let c = fold(c);
+            match s.chars().next() {
+                _ if c.is_none() => {}
+                Some(x) if Some(x) == c => s = &s[x.len_utf8()..],
+                Some(_) => return vec![],
+                None => {
+                    let data = self.collect_data_in(w, scope).into_iter();
+                    return data.map(|x| (x, len + i)).collect();
+                }
+            }
+        }
+        let len = len + l.len();
+        match self.edges_from(w).next() {
+            None if s.is_empty() => vec![(self.vertices[w].0.unwrap(), len)],
+            None => vec![],
+            Some(_) => { self.edges_from(w) }
+                .flat_map(|&e| self.find_folded_edge(s, e, len, fold, scope))
+                .collect(),
+        }
+    }
+
     pub(super) fn collect_data(&self, v: usize) -> Vec<Index> {
         let mut buffer = Vec::with_capacity(self.count_data(v));
         self.collect_data_to_buffer(v, &mut buffer);
@@ -91,6 +182,54 @@ impl<'a> SuffixTree<'a> {
         self.edges_from(v)
             .for_each(|&(_, w, _)| self.collect_data_to_buffer(w, buffer));
     }
+
+    /// Count the data below `v` into `counts`, by the range of rows in `starts` each of them is in,
+    /// counting the whole subtree at once when all of its rows are in one range.
+    pub(super) fn count_data_by(&self, v: usize, starts: &[usize], counts: &mut [usize]) {
+        let range = |i| starts.partition_point(|&j| j <= i).saturating_sub(1);
+        match self.vertices[v].3 {
+            (min, max) if range(min) == range(max) => counts[range(min)] += self.count_data(v),
+            _ => {
+                { self.vertices[v].0.into_iter() }.for_each(|(i, _)| counts[range(i)] += 1);
+                { self.edges_from(v) }.for_each(|&(_, w, _)| self.count_data_by(w, starts, counts));
+            }
+        }
+    }
+
+    /// Count the data below `v` in `scope` which are at the start of an aya,
+    /// skipping the subtrees outside `scope`.
+    pub(super) fn count_aya_starts_in(&self, v: usize, scope: &Scope) -> usize {
+        if !self.overlaps(v, scope) {
+            return 0;
+        }
+        let data = self.vertices[v]
+            .0
+            .filter(|&(i, j)| j == 0 && scope.contains(i));
+        let edges = self.edges_from(v);
+        data.into_iter().count()
+            + edges
+                .map(|&(_, w, _)| self.count_aya_starts_in(w, scope))
+                .sum::<usize>()
+    }
+
+    /// Collect the data below `v` in `scope`, skipping the subtrees outside `scope`.
+    pub(super) fn collect_data_in(&self, v: usize, scope: &Scope) -> Vec<Index> {
+        match self.vertices[v].3 {
+            rows if scope.covers(rows) => self.collect_data(v),
+            rows if !scope.overlaps(rows) => vec![],
+            _ => {
+                self.vertices[v]
+                    .0
+                    .filter(|&(i, _)| scope.contains(i))
+                    .into_iter()
+            }
+            .chain(
+                self.edges_from(v)
+                    .flat_map(|&(_, w, _)| self.collect_data_in(w, scope)),
+            )
+            .collect(),
+        }
+    }
 }
 
 fn contains_harf_muqottoah(i: Index) -> bool {
@@ -121,6 +260,58 @@ mod tests {
         assert_eq!(SuffixTree::longest_prefix("ax", "ay"), Some("a"));
     }
 
+    #[test]
+    fn test_data_rows() {
+        let q = Quranize::new();
+        let t = &q.tree;
+        let rows = |v| {
+            let data = t.collect_data(v).into_iter().map(|(i, _)| i);
+            (data.clone().min().unwrap(), data.max().unwrap())
+        };
+        assert_eq!(t.vertices[0].3, (0, AYA_COUNT - 1));
+        t.edges_from(0)
+            .take(50)
+            .for_each(|&(_, w, _)| assert_eq!(t.vertices[w].3, rows(w)));
+        let scope = Scope::suras(&[2, 114]);
+        let mut data = t.collect_data(0);
+        data.retain(|&(i, _)| scope.contains(i));
+        data.sort();
+        assert_eq!(t.count_data_in(0, &scope), data.len());
+        let mut scoped_data = t.collect_data_in(0, &scope);
+        scoped_data.sort();
+        assert_eq!(scoped_data, data);
+        let aya_starts = data.iter().filter(|&&(_, j)| j == 0).count();
+        assert_eq!(t.count_aya_starts_in(0, &scope), aya_starts);
+    }
+
+    #[test]
+    #[should_panic(expected = "row 1 is added after row 2")]
+    fn test_construct_decreasing_rows() {
+        let mut t = SuffixTree::with_capacity(8);
+        t.construct(0, "قُل هُوَ");
+        t.construct(2, "قُل");
+        t.construct(1, "هُوَ");
+    }
+
+    #[test]
+    fn test_count_data_by() {
+        let q = Quranize::new();
+        let t = &q.tree;
+        let v = t.locate("قُل", 0).unwrap();
+        assert_eq!(t.count_data(v), t.find("قُل", 0, &Scope::default()).len());
+        let mut counts = [0; 3];
+        t.count_data_by(v, &[0, 7, 293], &mut counts);
+        let data = t.collect_data(v);
+        assert_eq!(counts[0], 0);
+        assert_eq!(
+            counts[1],
+            data.iter().filter(|&&(i, _)| (7..293).contains(&i)).count()
+        );
+        assert_eq!(counts.iter().sum::<usize>(), data.len());
+        assert_eq!(t.locate("", 0), None);
+        assert_eq!(t.locate("qul", 0), None);
+    }
+
     #[test]
     fn test_contains_harf_muqottoah() {
         let q = Quranize::new();
@@ -1,8 +1,8 @@
-pub(super) fn suffix_iter(s: &str) -> SuffixIter {
+pub(crate) fn suffix_iter(s: &str) -> SuffixIter<'_> {
     SuffixIter { s, i: 0 }
 }
 
-pub(super) struct SuffixIter<'a> {
+pub(crate) struct SuffixIter<'a> {
     s: &'a str,
     i: usize,
 }
@@ -30,8 +30,13 @@ impl<'a> Iterator for SuffixIter<'a> {
     }
 }
 
-fn non_separator((_, c): &(usize, char)) -> bool {
-    !matches!(c, ' ' | '\u{06D6}'..='\u{06DC}' | '\n')
+fn non_separator(&(_, c): &(usize, char)) -> bool {
+    !is_separator(c)
+}
+
+/// Whether `c` separates words: a space, a waqf mark or the end of an aya.
+pub(crate) fn is_separator(c: char) -> bool {
+    matches!(c, ' ' | '\u{06D6}'..='\u{06DC}' | '\n')
 }
 
 #[cfg(test)]
@@ -0,0 +1,432 @@
+//! Annotating quran text with tajweed rules.
+//!
+//! # Examples
+//!
+//! ```
+//! use quranize::tajweed::{annotate, Madd, Rule};
+//!
+//! let q = quranize::Quranize::new();
+//! let aya = q.get_quran(6225).unwrap(); // قُل أَعوذُ بِرَبِّ الفَلَقِ
+//! let rules = Vec::from_iter(annotate(aya).into_iter().map(|s| (&aya[s.start..s.end], s.rule)));
+//! assert_eq!(rules[0], ("و", Rule::Madd(Madd::Thabii)));
+//! assert_eq!(rules.last(), Some(&("قِ", Rule::Qalqalah)));
+//! ```
+
+mod syllable;
+
+use crate::transliteration::chars::*;
+
+pub use syllable::{syllables, Syllable};
+
+/// Tajweed rule applied to a part of quran text.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rule {
+    /// Noon or meem with shadda, pronounced with a nasal sound.
+    Ghunnah,
+    /// Noon sakinah or tanween merged into the next letter, or meem sakinah merged into the next meem.
+    Idgham { ghunnah: bool },
+    /// Noon sakinah or tanween, or meem sakinah before beh, pronounced hidden.
+    Ikhfa,
+    /// Noon sakinah or tanween before beh, pronounced as meem.
+    Iqlab,
+    /// Noon sakinah or tanween, or meem sakinah, pronounced clearly.
+    Izhar,
+    /// Letter of qalqalah without a vowel, pronounced with an echo.
+    Qalqalah,
+    /// Elongation of a vowel.
+    Madd(Madd),
+    /// Lam of the definite article assimilated into the next letter.
+    LamShamsiyah,
+    /// Lam of the definite article pronounced clearly.
+    LamQamariyah,
+}
+
+/// Kind of [`Rule::Madd`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Madd {
+    /// Madd thabi'i, the natural elongation.
+    Thabii,
+    /// Madd badal, a long vowel right after a hamza.
+    Badal,
+    /// Madd 'iwad, a tanween fathah at the end of an aya, except on teh marbuta,
+    /// which is read as a sakin heh.
+    Iwad,
+    /// Madd wajib muttasil, a long vowel followed by a hamza in the same word.
+    Muttasil,
+    /// Madd jaiz munfasil, a long vowel at the end of a word followed by a hamza.
+    Munfasil,
+    /// Madd 'aridh lissukun, a long vowel before the last letter of an aya.
+    Arid,
+    /// Madd lin, a diphthong before the last letter of an aya.
+    Lin,
+    /// Madd lazim, a long vowel followed by a shadda in the same word.
+    Lazim,
+}
+
+impl Madd {
+    /// Allowed lengths of this madd, in harakah counts, the preferred one first.
+    pub fn lengths(self) -> &'static [u8] {
+        match self {
+            Self::Thabii | Self::Badal | Self::Iwad => &[2],
+            Self::Muttasil => &[4, 5],
+            Self::Munfasil => &[4, 5, 2],
+            Self::Arid | Self::Lin => &[4, 2, 6],
+            Self::Lazim => &[6],
+        }
+    }
+}
+
+/// Byte range of quran text, tagged with the tajweed rule applied on it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Span {
+    /// Byte offset where the span starts.
+    pub start: usize,
+    /// Byte offset where the span ends, exclusive.
+    pub end: usize,
+    /// Tajweed rule applied on the span.
+    pub rule: Rule,
+}
+
+/// Annotate `text`, e.g. an aya returned by [`crate::Quranize::get_quran`], with tajweed rules.
+///
+/// Each span covers a letter and its marks, and the spans are ordered by their position.
+/// Rules across aya boundaries are not annotated, since reciters stop at the end of an aya.
+pub fn annotate(text: &str) -> Vec<Span> {
+    let clusters = clusters(text);
+    let annotator = Annotator {
+        clusters: &clusters,
+    };
+    { clusters.iter().enumerate() }
+        .flat_map(|(i, c)| annotator.rules(i).map(|rule| (c.start, c.end, rule)))
+        .map(|(start, end, rule)| Span { start, end, rule })
+        .collect()
+}
+
+/// A letter, or a separator, together with the marks on it.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Cluster {
+    pub(crate) start: usize,
+    pub(crate) end: usize,
+    pub(crate) letter: char,
+    pub(crate) harakah: Option<char>,
+    pub(crate) shadda: bool,
+    pub(crate) superscript_alef: bool,
+}
+
+impl Cluster {
+    pub(crate) fn is_separator(&self) -> bool {
+        matches!(self.letter, SPACE | '\n') || is_waqf_mark(self.letter)
+    }
+
+    pub(crate) fn is_bare(&self) -> bool {
+        self.harakah.is_none() && !self.shadda && !self.superscript_alef
+    }
+
+    fn has_tanween(&self) -> bool {
+        matches!(self.harakah, Some(FATHATAN..=KASRATAN))
+    }
+}
+
+pub(crate) fn clusters(text: &str) -> Vec<Cluster> {
+    let mut clusters: Vec<Cluster> = Vec::with_capacity(text.len() / 2);
+    for (i, c) in text.char_indices() {
+        let end = i + c.len_utf8();
+        match clusters.last_mut() {
+            Some(last) if is_mark(c) && !last.is_separator() => {
+                last.end = end;
+                match c {
+                    SHADDA => last.shadda = true,
+                    LETTER_SUPERSCRIPT_ALEF => last.superscript_alef = true,
+                    HAMZA_ABOVE | TATWEEL => {}
+                    c => last.harakah = Some(c),
+                }
+            }
+            _ => clusters.push(Cluster {
+                start: i,
+                end,
+                letter: c,
+                ..Default::default()
+            }),
+        }
+    }
+    clusters
+}
+
+struct Annotator<'a> {
+    clusters: &'a [Cluster],
+}
+
+impl Annotator<'_> {
+    fn rules(&self, i: usize) -> impl Iterator<Item = Rule> {
+        let c = &self.clusters[i];
+        let rules = [
+            self.nunation(i),
+            self.meem_sakinah(i),
+            (matches!(c.letter, LETTER_NOON | LETTER_MEEM) && c.shadda).then_some(Rule::Ghunnah),
+            self.qalqalah(i),
+            self.lam(i),
+            self.madd(i).map(Rule::Madd),
+        ];
+        rules.into_iter().flatten()
+    }
+
+    fn get(&self, i: usize) -> Option<&Cluster> {
+        self.clusters.get(i)
+    }
+
+    fn letter(&self, i: usize) -> Option<char> {
+        Some(self.get(i)?.letter)
+    }
+
+    /// The next letter after `i`, skipping word breaks, but not the end of aya.
+    fn next_letter(&self, i: usize) -> Option<(usize, &Cluster)> {
+        { self.clusters.iter().enumerate().skip(i + 1) }
+            .find(|(_, c)| c.letter == '\n' || !c.is_separator())
+            .filter(|(_, c)| c.letter != '\n')
+    }
+
+    fn is_word_start(&self, i: usize) -> bool {
+        i == 0 || self.clusters[i - 1].is_separator()
+    }
+
+    fn is_word_end(&self, i: usize) -> bool {
+        self.get(i + 1).is_none_or(Cluster::is_separator)
+    }
+
+    fn is_aya_end(&self, i: usize) -> bool {
+        matches!(self.letter(i + 1), None | Some('\n'))
+    }
+
+    /// Whether `i` is a letter without a vowel. Short vowels before a long vowel are mostly
+    /// left unwritten, so a bare letter followed by a long vowel is not sakin.
+    fn is_sakin(&self, i: usize) -> bool {
+        let c = &self.clusters[i];
+        let is_vowel_letter = matches!(c.letter, LETTER_ALEF | LETTER_ALEF_MAKSURA);
+        let before_long_vowel =
+            self.get(i + 1).is_some_and(Cluster::is_bare) && self.madd(i + 1).is_some();
+        c.is_bare() && !c.is_separator() && !is_vowel_letter && !before_long_vowel
+    }
+
+    fn nunation(&self, i: usize) -> Option<Rule> {
+        let c = &self.clusters[i];
+        let i = match () {
+            _ if c.letter == LETTER_NOON && self.is_sakin(i) => i,
+            _ if c.has_tanween() => match self.get(i + 1) {
+                Some(s) if matches!(s.letter, LETTER_ALEF | LETTER_ALEF_MAKSURA) && s.is_bare() => {
+                    i + 1
+                }
+                _ => i,
+            },
+            _ => return None,
+        };
+        let (j, next) = self.next_letter(i)?;
+        let within_word = j == i + 1;
+        match next.letter {
+            n if is_throat_letter(n) => Some(Rule::Izhar),
+            LETTER_BEH => Some(Rule::Iqlab),
+            LETTER_YEH | LETTER_NOON | LETTER_MEEM | LETTER_WAW if !within_word => {
+                Some(Rule::Idgham { ghunnah: true })
+            }
+            LETTER_LAM | LETTER_REH if !within_word => Some(Rule::Idgham { ghunnah: false }),
+            n if is_ikhfa_letter(n) => Some(Rule::Ikhfa),
+            _ => Some(Rule::Izhar),
+        }
+    }
+
+    fn meem_sakinah(&self, i: usize) -> Option<Rule> {
+        let c = &self.clusters[i];
+        if c.letter != LETTER_MEEM || !self.is_sakin(i) {
+            return None;
+        }
+        match self.next_letter(i)?.1.letter {
+            LETTER_BEH => Some(Rule::Ikhfa),
+            LETTER_MEEM => Some(Rule::Idgham { ghunnah: true }),
+            _ => Some(Rule::Izhar),
+        }
+    }
+
+    fn qalqalah(&self, i: usize) -> Option<Rule> {
+        let c = &self.clusters[i];
+        let stopped = self.is_sakin(i) || (self.is_aya_end(i) && !c.shadda);
+        (is_qalqalah_letter(c.letter) && stopped).then_some(Rule::Qalqalah)
+    }
+
+    /// Whether `i` is the alef of a definite article, i.e. a bare alef followed by a bare lam,
+    /// at the start of a word or after a one letter prefix.
+    fn is_article_alef(&self, i: usize) -> bool {
+        let is_bare = |i, letter| {
+            self.get(i)
+                .is_some_and(|c| c.letter == letter && c.is_bare())
+        };
+        let is_prefix = |i: usize| {
+            let c = &self.clusters[i];
+            c.harakah.is_some()
+                && matches!(c.letter, LETTER_WAW | LETTER_FEH | LETTER_BEH | LETTER_KAF)
+                && self.is_word_start(i)
+        };
+        is_bare(i, LETTER_ALEF)
+            && is_bare(i + 1, LETTER_LAM)
+            && (self.is_word_start(i) || is_prefix(i - 1))
+    }
+
+    /// Whether `i` ends a word followed by a hamzat wasl, dropping the long vowel at `i`.
+    fn is_before_wasl(&self, i: usize) -> bool {
+        let is_wasl = |(j, n): (usize, &Cluster)| {
+            n.letter == LETTER_ALEF && n.is_bare() && self.is_word_start(j)
+        };
+        self.is_word_end(i) && self.next_letter(i).is_some_and(is_wasl)
+    }
+
+    /// Whether the bare waw or yeh at `i` is a consonant carrying an unwritten fatha.
+    fn carries_vowel(&self, i: usize) -> bool {
+        match self.get(i + 1) {
+            Some(n) if n.letter == LETTER_ALEF_MAKSURA => true,
+            Some(n) if n.letter == LETTER_ALEF && n.is_bare() => !self.is_word_end(i + 1),
+            _ => false,
+        }
+    }
+
+    fn lam(&self, i: usize) -> Option<Rule> {
+        if i == 0 || !self.is_article_alef(i - 1) {
+            return None;
+        }
+        match self.letter(i + 1)? {
+            n if is_sun_letter(n) => Some(Rule::LamShamsiyah),
+            n if is_moon_letter(n) => Some(Rule::LamQamariyah),
+            _ => None,
+        }
+    }
+
+    fn madd(&self, i: usize) -> Option<Madd> {
+        let c = &self.clusters[i];
+        let prev = i
+            .checked_sub(1)
+            .and_then(|i| self.get(i))
+            .filter(|p| !p.is_separator());
+        let is_long = match c.letter {
+            _ if c.superscript_alef => true,
+            LETTER_ALEF_WITH_MADDA_ABOVE => true,
+            LETTER_ALEF | LETTER_ALEF_MAKSURA if c.is_bare() => match prev {
+                Some(p) if p.has_tanween() => {
+                    let iwad = p.harakah == Some(FATHATAN) && self.is_aya_end(i);
+                    return iwad.then_some(Madd::Iwad);
+                }
+                Some(p) if p.letter == LETTER_WAW && p.is_bare() => !self.is_word_end(i),
+                Some(_) => !self.is_article_alef(i),
+                None => false,
+            },
+            LETTER_WAW | LETTER_YEH if c.is_bare() => {
+                let vowel = if c.letter == LETTER_WAW { DAMMA } else { KASRA };
+                match prev {
+                    Some(p) if p.harakah == Some(FATHA) => {
+                        return (self.is_aya_end(i + 1) && !self.is_aya_end(i))
+                            .then_some(Madd::Lin);
+                    }
+                    Some(p) => p.harakah.is_none_or(|h| h == vowel) && !self.carries_vowel(i),
+                    None => false,
+                }
+            }
+            LETTER_TEH_MARBUTA => false,
+            _ if c.harakah == Some(FATHATAN) && self.is_aya_end(i) => return Some(Madd::Iwad),
+            _ => false,
+        };
+        if !is_long || self.is_before_wasl(i) {
+            return None;
+        }
+        let next = self.get(i + 1);
+        match () {
+            _ if next.is_some_and(|n| !n.is_separator() && n.shadda) => Some(Madd::Lazim),
+            _ if next.is_some_and(|n| is_hamza(n.letter)) => Some(Madd::Muttasil),
+            _ if self.is_word_end(i) && !self.is_aya_end(i) => {
+                let next = self.next_letter(i).map(|(_, n)| n.letter);
+                match next.is_some_and(is_hamza) {
+                    true => Some(Madd::Munfasil),
+                    false => Some(Madd::Thabii),
+                }
+            }
+            _ if !self.is_word_end(i) && self.is_aya_end(i + 1) => Some(Madd::Arid),
+            _ if prev.is_some_and(|p| is_hamza(p.letter)) => Some(Madd::Badal),
+            _ if c.letter == LETTER_ALEF_WITH_MADDA_ABOVE => Some(Madd::Badal),
+            _ => Some(Madd::Thabii),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Quranize;
+    use pretty_assertions::assert_eq;
+
+    fn rules(text: &str) -> Vec<(&str, Rule)> {
+        let spans = annotate(text).into_iter();
+        spans.map(|s| (&text[s.start..s.end], s.rule)).collect()
+    }
+
+    #[test]
+    fn test_annotate_al_fatihah() {
+        let q = Quranize::new();
+        assert_eq!(
+            rules(q.get_quran(0).unwrap()),
+            [
+                ("ل", Rule::LamShamsiyah),
+                ("ل", Rule::LamShamsiyah),
+                ("مـٰ", Rule::Madd(Madd::Thabii)),
+                ("ل", Rule::LamShamsiyah),
+                ("ي", Rule::Madd(Madd::Arid)),
+            ],
+        );
+        assert_eq!(
+            rules(q.get_quran(6).unwrap()),
+            [
+                ("ا", Rule::Madd(Madd::Thabii)),
+                ("ي", Rule::Madd(Madd::Thabii)),
+                ("ن", Rule::Izhar),
+                ("م", Rule::Izhar),
+                ("م", Rule::Izhar),
+                ("ل", Rule::LamQamariyah),
+                ("و", Rule::Madd(Madd::Thabii)),
+                ("م", Rule::Izhar),
+                ("ل", Rule::LamShamsiyah),
+                ("ا", Rule::Madd(Madd::Lazim)),
+                ("ي", Rule::Madd(Madd::Arid)),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_annotate_al_falaq() {
+        let q = Quranize::new();
+        assert_eq!(
+            rules(q.get_quran(6227).unwrap()),
+            [
+                ("ن", Rule::Ikhfa),
+                ("ا", Rule::Madd(Madd::Thabii)),
+                ("قٍ", Rule::Izhar),
+                ("ا", Rule::Madd(Madd::Thabii)),
+                ("بَ", Rule::Qalqalah),
+            ],
+        );
+        assert_eq!(
+            rules(q.get_quran(6228).unwrap())[2..4],
+            [("نَّ", Rule::Ghunnah), ("ا", Rule::Madd(Madd::Thabii))],
+        );
+        assert_eq!(rules("إِن يَشَأ"), [("ن", Rule::Idgham { ghunnah: true })]);
+        assert_eq!(rules("مِن بَعدِ"), [("ن", Rule::Iqlab), ("دِ", Rule::Qalqalah)],);
+        assert_eq!(
+            rules("سَماءً"),
+            [
+                ("ا", Rule::Madd(Madd::Muttasil)),
+                ("ءً", Rule::Madd(Madd::Iwad))
+            ]
+        );
+        assert_eq!(rules("رَحمَةً"), []);
+        assert_eq!(
+            rules("لَهُم مَا"),
+            [
+                ("م", Rule::Idgham { ghunnah: true }),
+                ("ا", Rule::Madd(Madd::Thabii))
+            ],
+        );
+    }
+}
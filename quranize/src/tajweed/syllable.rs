@@ -0,0 +1,206 @@
+use super::{Annotator, Cluster, Madd, Rule};
+use crate::transliteration::chars::*;
+
+/// Syllable of a word: a consonant with its vowel (CV), optionally closed by a consonant (CVC).
+///
+/// A long vowel is part of the syllable of the consonant before it, and its [`Madd`] is attached.
+/// Letters which are not pronounced, e.g. the lam of lam shamsiyah, are part of the syllable before them,
+/// as is a hamzat wasl, which joins a word to the syllable ending the word before it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Syllable {
+    /// Byte offset where the syllable starts.
+    pub start: usize,
+    /// Byte offset where the syllable ends, exclusive.
+    pub end: usize,
+    /// Whether the syllable is closed by a consonant (CVC).
+    pub closed: bool,
+    /// Madd of the long vowel of the syllable, if any.
+    pub madd: Option<Madd>,
+}
+
+impl Syllable {
+    /// Length of the vowel in harakah counts, i.e. the preferred length of its madd, or 1.
+    pub fn length(&self) -> u8 {
+        self.madd.map_or(1, |m| m.lengths()[0])
+    }
+}
+
+/// Split the words of `text` into syllables, as pronounced when stopping at the end of an aya.
+///
+/// # Examples
+///
+/// ```
+/// let q = quranize::Quranize::new();
+/// let aya = q.get_quran(6225).unwrap(); // قُل أَعوذُ بِرَبِّ الفَلَقِ
+/// let syllables = quranize::tajweed::syllables(aya);
+/// let texts = Vec::from_iter(syllables.iter().map(|s| &aya[s.start..s.end]));
+/// assert_eq!(texts, ["قُل", "أَ", "عو", "ذُ", "بِ", "رَ", "بِّ ال", "فَ", "لَقِ"]);
+/// assert_eq!(syllables[2].length(), 2);
+/// ```
+pub fn syllables(text: &str) -> Vec<Syllable> {
+    let clusters = super::clusters(text);
+    let annotator = Annotator {
+        clusters: &clusters,
+    };
+    let mut syllables: Vec<Syllable> = vec![];
+    let mut in_word = false;
+    for (i, c) in clusters.iter().enumerate() {
+        if c.is_separator() {
+            in_word = false;
+            continue;
+        }
+        let role = annotator.role(i);
+        let is_wasl = matches!(role, Role::Silent) && annotator.is_word_start(i);
+        let last = syllables.last_mut().filter(|_| in_word || is_wasl);
+        match (role, last) {
+            (Role::Nucleus, Some(last)) => {
+                last.end = c.end;
+                last.closed = false;
+                last.madd = annotator.madd(i);
+            }
+            (Role::Coda, Some(last)) => {
+                last.end = c.end;
+                last.closed = true;
+                last.madd = last.madd.or(annotator.madd(i));
+            }
+            (Role::Silent, Some(last)) => last.end = c.end,
+            (_, last) => {
+                if let Some(last) = last.filter(|_| c.shadda) {
+                    last.closed = true;
+                }
+                let madd = annotator.madd(i);
+                syllables.push(Syllable {
+                    start: c.start,
+                    end: c.end,
+                    closed: c.has_tanween() && madd.is_none(),
+                    madd,
+                });
+            }
+        }
+        in_word = true;
+    }
+    syllables
+}
+
+/// Part of a syllable a letter takes.
+enum Role {
+    Onset,
+    Nucleus,
+    Coda,
+    Silent,
+}
+
+impl Annotator<'_> {
+    fn role(&self, i: usize) -> Role {
+        let c = &self.clusters[i];
+        let is_vowel_letter = c.is_bare()
+            && matches!(
+                c.letter,
+                LETTER_ALEF | LETTER_ALEF_MAKSURA | LETTER_WAW | LETTER_YEH
+            );
+        let madd = self.madd(i);
+        match c.letter {
+            _ if madd == Some(Madd::Lin) => Role::Coda,
+            _ if madd.is_some() && is_vowel_letter => Role::Nucleus,
+            LETTER_ALEF if c.is_bare() && self.is_word_start(i) => match self.is_after_word(i) {
+                true => Role::Silent,
+                false => Role::Onset,
+            },
+            LETTER_ALEF | LETTER_ALEF_MAKSURA if c.is_bare() => Role::Silent,
+            LETTER_WAW | LETTER_YEH if is_vowel_letter && self.is_dropped(i) => Role::Silent,
+            _ if self.lam(i) == Some(Rule::LamShamsiyah) => Role::Silent,
+            _ if self.is_aya_end(i) && !c.superscript_alef && madd.is_none() => Role::Coda,
+            _ if self.is_sakin(i) => Role::Coda,
+            _ => Role::Onset,
+        }
+    }
+
+    /// Whether the word starting at `i` is read on from a word before it in the same aya.
+    fn is_after_word(&self, i: usize) -> bool {
+        { self.clusters[..i].iter().rev() }
+            .find(|c| c.letter == '\n' || !c.is_separator())
+            .is_some_and(|c| c.letter != '\n')
+    }
+
+    /// Whether the bare waw or yeh at `i` is a long vowel dropped before a hamzat wasl.
+    fn is_dropped(&self, i: usize) -> bool {
+        let vowel = match self.clusters[i].letter {
+            LETTER_WAW => DAMMA,
+            _ => KASRA,
+        };
+        let prev = i.checked_sub(1).and_then(|i| self.get(i));
+        let is_vowel = |p: &Cluster| !p.is_separator() && p.harakah.is_none_or(|h| h == vowel);
+        prev.is_some_and(is_vowel) && self.is_before_wasl(i)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Quranize;
+    use pretty_assertions::assert_eq;
+
+    fn syllable_texts(text: &str) -> Vec<(&str, bool, u8)> {
+        let syllables = syllables(text).into_iter();
+        syllables
+            .map(|s| (&text[s.start..s.end], s.closed, s.length()))
+            .collect()
+    }
+
+    #[test]
+    fn test_syllables() {
+        let q = Quranize::new();
+        assert_eq!(
+            syllable_texts(q.get_quran(0).unwrap()),
+            [
+                ("بِس", true, 1),
+                ("مِ ال", true, 1),
+                ("لَّ", false, 1),
+                ("هِ ال", true, 1),
+                ("رَّح", true, 1),
+                ("مـٰ", false, 2),
+                ("نِ ال", true, 1),
+                ("رَّ", false, 1),
+                ("حيمِ", true, 4),
+            ],
+        );
+        assert_eq!(
+            syllable_texts(q.get_quran(6228).unwrap()),
+            [
+                ("وَ", false, 1),
+                ("مِن", true, 1),
+                ("شَ", true, 1),
+                ("رِّ ال", true, 1),
+                ("نَّ", true, 1),
+                ("فّا", false, 2),
+                ("ثا", false, 2),
+                ("تِ", false, 1),
+                ("فِي ال", true, 1),
+                ("عُ", false, 1),
+                ("قَدِ", true, 1),
+            ],
+        );
+        assert_eq!(
+            syllable_texts("وَلَا الضّالّينَ\n"),
+            [
+                ("وَ", false, 1),
+                ("لَا ال", true, 1),
+                ("ضّا", true, 6),
+                ("لّينَ", true, 4),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_syllable_madd() {
+        let madds = |text| Vec::from_iter(syllables(text).into_iter().filter_map(|s| s.madd));
+        assert_eq!(madds("جاءَ"), [Madd::Muttasil]);
+        assert_eq!(madds("بِما أُنزِلَ"), [Madd::Munfasil]);
+        assert_eq!(madds("عَلِيمًا"), [Madd::Thabii, Madd::Iwad]);
+        assert_eq!(madds("قُرَيشٍ"), [Madd::Lin]);
+        assert_eq!(
+            Vec::from_iter(madds("آمَنوا بِهِ").into_iter().map(Madd::lengths)),
+            [&[2][..], &[2]],
+        );
+    }
+}
@@ -1,53 +1,221 @@
-pub(super) const SPACE: char = '\u{0020}';
-
-pub(super) const LETTER_HAMZA: char = '\u{0621}';
-pub(super) const LETTER_ALEF_WITH_MADDA_ABOVE: char = '\u{0622}';
-pub(super) const LETTER_ALEF_WITH_HAMZA_ABOVE: char = '\u{0623}';
-pub(super) const LETTER_WAW_WITH_HAMZA_ABOVE: char = '\u{0624}';
-pub(super) const LETTER_ALEF_WITH_HAMZA_BELOW: char = '\u{0625}';
-pub(super) const LETTER_YEH_WITH_HAMZA_ABOVE: char = '\u{0626}';
-pub(super) const LETTER_ALEF: char = '\u{0627}';
-pub(super) const LETTER_BEH: char = '\u{0628}';
-pub(super) const LETTER_TEH_MARBUTA: char = '\u{0629}';
-pub(super) const LETTER_TEH: char = '\u{062A}';
-pub(super) const LETTER_THEH: char = '\u{062B}';
-pub(super) const LETTER_JEEM: char = '\u{062C}';
-pub(super) const LETTER_HAH: char = '\u{062D}';
-pub(super) const LETTER_KHAH: char = '\u{062E}';
-pub(super) const LETTER_DAL: char = '\u{062F}';
-pub(super) const LETTER_THAL: char = '\u{0630}';
-pub(super) const LETTER_REH: char = '\u{0631}';
-pub(super) const LETTER_ZAIN: char = '\u{0632}';
-pub(super) const LETTER_SEEN: char = '\u{0633}';
-pub(super) const LETTER_SHEEN: char = '\u{0634}';
-pub(super) const LETTER_SAD: char = '\u{0635}';
-pub(super) const LETTER_DAD: char = '\u{0636}';
-pub(super) const LETTER_TAH: char = '\u{0637}';
-pub(super) const LETTER_ZAH: char = '\u{0638}';
-pub(super) const LETTER_AIN: char = '\u{0639}';
-pub(super) const LETTER_GHAIN: char = '\u{063A}';
-
-pub(super) const TATWEEL: char = '\u{0640}';
-
-pub(super) const LETTER_FEH: char = '\u{0641}';
-pub(super) const LETTER_QAF: char = '\u{0642}';
-pub(super) const LETTER_KAF: char = '\u{0643}';
-pub(super) const LETTER_LAM: char = '\u{0644}';
-pub(super) const LETTER_MEEM: char = '\u{0645}';
-pub(super) const LETTER_NOON: char = '\u{0646}';
-pub(super) const LETTER_HEH: char = '\u{0647}';
-pub(super) const LETTER_WAW: char = '\u{0648}';
-pub(super) const LETTER_ALEF_MAKSURA: char = '\u{0649}';
-pub(super) const LETTER_YEH: char = '\u{064A}';
-
-pub(super) const FATHATAN: char = '\u{064B}';
-pub(super) const DAMMATAN: char = '\u{064C}';
-pub(super) const KASRATAN: char = '\u{064D}';
-pub(super) const FATHA: char = '\u{064E}';
-pub(super) const DAMMA: char = '\u{064F}';
-pub(super) const KASRA: char = '\u{0650}';
-pub(super) const SHADDA: char = '\u{0651}';
-
-pub(super) const HAMZA_ABOVE: char = '\u{0654}';
-pub(super) const LETTER_SUPERSCRIPT_ALEF: char = '\u{0670}';
-pub(super) const EMPTY_CENTRE_LOW_STOP: char = '\u{06EA}';
+pub(crate) const SPACE: char = '\u{0020}';
+
+pub(crate) const LETTER_HAMZA: char = '\u{0621}';
+pub(crate) const LETTER_ALEF_WITH_MADDA_ABOVE: char = '\u{0622}';
+pub(crate) const LETTER_ALEF_WITH_HAMZA_ABOVE: char = '\u{0623}';
+pub(crate) const LETTER_WAW_WITH_HAMZA_ABOVE: char = '\u{0624}';
+pub(crate) const LETTER_ALEF_WITH_HAMZA_BELOW: char = '\u{0625}';
+pub(crate) const LETTER_YEH_WITH_HAMZA_ABOVE: char = '\u{0626}';
+pub(crate) const LETTER_ALEF: char = '\u{0627}';
+pub(crate) const LETTER_BEH: char = '\u{0628}';
+pub(crate) const LETTER_TEH_MARBUTA: char = '\u{0629}';
+pub(crate) const LETTER_TEH: char = '\u{062A}';
+pub(crate) const LETTER_THEH: char = '\u{062B}';
+pub(crate) const LETTER_JEEM: char = '\u{062C}';
+pub(crate) const LETTER_HAH: char = '\u{062D}';
+pub(crate) const LETTER_KHAH: char = '\u{062E}';
+pub(crate) const LETTER_DAL: char = '\u{062F}';
+pub(crate) const LETTER_THAL: char = '\u{0630}';
+pub(crate) const LETTER_REH: char = '\u{0631}';
+pub(crate) const LETTER_ZAIN: char = '\u{0632}';
+pub(crate) const LETTER_SEEN: char = '\u{0633}';
+pub(crate) const LETTER_SHEEN: char = '\u{0634}';
+pub(crate) const LETTER_SAD: char = '\u{0635}';
+pub(crate) const LETTER_DAD: char = '\u{0636}';
+pub(crate) const LETTER_TAH: char = '\u{0637}';
+pub(crate) const LETTER_ZAH: char = '\u{0638}';
+pub(crate) const LETTER_AIN: char = '\u{0639}';
+pub(crate) const LETTER_GHAIN: char = '\u{063A}';
+
+pub(crate) const TATWEEL: char = '\u{0640}';
+
+pub(crate) const LETTER_FEH: char = '\u{0641}';
+pub(crate) const LETTER_QAF: char = '\u{0642}';
+pub(crate) const LETTER_KAF: char = '\u{0643}';
+pub(crate) const LETTER_LAM: char = '\u{0644}';
+pub(crate) const LETTER_MEEM: char = '\u{0645}';
+pub(crate) const LETTER_NOON: char = '\u{0646}';
+pub(crate) const LETTER_HEH: char = '\u{0647}';
+pub(crate) const LETTER_WAW: char = '\u{0648}';
+pub(crate) const LETTER_ALEF_MAKSURA: char = '\u{0649}';
+pub(crate) const LETTER_YEH: char = '\u{064A}';
+
+pub(crate) const FATHATAN: char = '\u{064B}';
+pub(crate) const DAMMATAN: char = '\u{064C}';
+pub(crate) const KASRATAN: char = '\u{064D}';
+pub(crate) const FATHA: char = '\u{064E}';
+pub(crate) const DAMMA: char = '\u{064F}';
+pub(crate) const KASRA: char = '\u{0650}';
+pub(crate) const SHADDA: char = '\u{0651}';
+
+pub(crate) const HAMZA_ABOVE: char = '\u{0654}';
+pub(crate) const LETTER_SUPERSCRIPT_ALEF: char = '\u{0670}';
+pub(crate) const SMALL_HIGH_LIGATURE_SAD_WITH_LAM_WITH_ALEF_MAKSURA: char = '\u{06D6}';
+pub(crate) const SMALL_HIGH_LIGATURE_QAF_WITH_LAM_WITH_ALEF_MAKSURA: char = '\u{06D7}';
+pub(crate) const SMALL_HIGH_MEEM_INITIAL_FORM: char = '\u{06D8}';
+pub(crate) const SMALL_HIGH_LAM_ALEF: char = '\u{06D9}';
+pub(crate) const SMALL_HIGH_JEEM: char = '\u{06DA}';
+pub(crate) const SMALL_HIGH_THREE_DOTS: char = '\u{06DB}';
+pub(crate) const SMALL_HIGH_SEEN: char = '\u{06DC}';
+pub(crate) const PLACE_OF_SAJDAH: char = '\u{06E9}';
+pub(crate) const EMPTY_CENTRE_LOW_STOP: char = '\u{06EA}';
+
+pub(crate) const KNOWN_CHARS: [char; 56] = [
+    SPACE,
+    LETTER_HAMZA,
+    LETTER_ALEF_WITH_MADDA_ABOVE,
+    LETTER_ALEF_WITH_HAMZA_ABOVE,
+    LETTER_WAW_WITH_HAMZA_ABOVE,
+    LETTER_ALEF_WITH_HAMZA_BELOW,
+    LETTER_YEH_WITH_HAMZA_ABOVE,
+    LETTER_ALEF,
+    LETTER_BEH,
+    LETTER_TEH_MARBUTA,
+    LETTER_TEH,
+    LETTER_THEH,
+    LETTER_JEEM,
+    LETTER_HAH,
+    LETTER_KHAH,
+    LETTER_DAL,
+    LETTER_THAL,
+    LETTER_REH,
+    LETTER_ZAIN,
+    LETTER_SEEN,
+    LETTER_SHEEN,
+    LETTER_SAD,
+    LETTER_DAD,
+    LETTER_TAH,
+    LETTER_ZAH,
+    LETTER_AIN,
+    LETTER_GHAIN,
+    TATWEEL,
+    LETTER_FEH,
+    LETTER_QAF,
+    LETTER_KAF,
+    LETTER_LAM,
+    LETTER_MEEM,
+    LETTER_NOON,
+    LETTER_HEH,
+    LETTER_WAW,
+    LETTER_ALEF_MAKSURA,
+    LETTER_YEH,
+    FATHATAN,
+    DAMMATAN,
+    KASRATAN,
+    FATHA,
+    DAMMA,
+    KASRA,
+    SHADDA,
+    HAMZA_ABOVE,
+    LETTER_SUPERSCRIPT_ALEF,
+    SMALL_HIGH_LIGATURE_SAD_WITH_LAM_WITH_ALEF_MAKSURA,
+    SMALL_HIGH_LIGATURE_QAF_WITH_LAM_WITH_ALEF_MAKSURA,
+    SMALL_HIGH_MEEM_INITIAL_FORM,
+    SMALL_HIGH_LAM_ALEF,
+    SMALL_HIGH_JEEM,
+    SMALL_HIGH_THREE_DOTS,
+    SMALL_HIGH_SEEN,
+    PLACE_OF_SAJDAH,
+    EMPTY_CENTRE_LOW_STOP,
+];
+
+pub(crate) fn is_sun_letter(c: char) -> bool {
+    matches!(
+        c,
+        LETTER_TEH
+            | LETTER_THEH
+            | LETTER_DAL
+            | LETTER_THAL
+            | LETTER_REH
+            | LETTER_ZAIN
+            | LETTER_SEEN
+            | LETTER_SHEEN
+            | LETTER_SAD
+            | LETTER_DAD
+            | LETTER_TAH
+            | LETTER_ZAH
+            | LETTER_LAM
+            | LETTER_NOON
+    )
+}
+
+pub(crate) fn is_moon_letter(c: char) -> bool {
+    matches!(
+        c,
+        LETTER_HAMZA
+            ..=LETTER_BEH
+                | LETTER_JEEM
+                | LETTER_HAH
+                | LETTER_KHAH
+                | LETTER_AIN
+                | LETTER_GHAIN
+                | LETTER_FEH
+                | LETTER_QAF
+                | LETTER_KAF
+                | LETTER_MEEM
+                | LETTER_HEH
+                | LETTER_WAW
+                | LETTER_YEH
+    )
+}
+
+pub(crate) fn is_ikhfa_letter(c: char) -> bool {
+    matches!(
+        c,
+        LETTER_TEH
+            | LETTER_THEH
+            | LETTER_JEEM
+            | LETTER_DAL
+            | LETTER_THAL
+            | LETTER_ZAIN
+            | LETTER_SEEN
+            | LETTER_SHEEN
+            | LETTER_SAD
+            | LETTER_DAD
+            | LETTER_TAH
+            | LETTER_ZAH
+            | LETTER_FEH
+            | LETTER_QAF
+            | LETTER_KAF
+    )
+}
+
+pub(crate) fn is_throat_letter(c: char) -> bool {
+    is_hamza(c)
+        || matches!(
+            c,
+            LETTER_HAH | LETTER_KHAH | LETTER_AIN | LETTER_GHAIN | LETTER_HEH
+        )
+}
+
+pub(crate) fn is_hamza(c: char) -> bool {
+    matches!(c, LETTER_HAMZA..=LETTER_YEH_WITH_HAMZA_ABOVE | HAMZA_ABOVE)
+}
+
+pub(crate) fn is_qalqalah_letter(c: char) -> bool {
+    matches!(
+        c,
+        LETTER_QAF | LETTER_TAH | LETTER_BEH | LETTER_JEEM | LETTER_DAL
+    )
+}
+
+pub(crate) fn is_harakah(c: char) -> bool {
+    matches!(c, FATHATAN..=KASRA)
+}
+
+/// Whether `c` is written on the letter before it: a harakah, a shadda, a superscript alef,
+/// a hamza above, or a tatweel carrying those.
+pub(crate) fn is_mark(c: char) -> bool {
+    is_harakah(c) || matches!(c, SHADDA | LETTER_SUPERSCRIPT_ALEF | HAMZA_ABOVE | TATWEEL)
+}
+
+pub(crate) fn is_arabic_letter(c: char) -> bool {
+    matches!(c, LETTER_HAMZA..=LETTER_YEH) && c != TATWEEL
+}
+
+pub(crate) fn is_waqf_mark(c: char) -> bool {
+    matches!(
+        c,
+        SMALL_HIGH_LIGATURE_SAD_WITH_LAM_WITH_ALEF_MAKSURA..=SMALL_HIGH_SEEN
+    )
+}
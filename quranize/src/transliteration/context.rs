@@ -0,0 +1,110 @@
+use super::chars::*;
+
+/// Number of preceding quran characters visible to [`super::ContextualRule::before`].
+pub(crate) const CONTEXT_SIZE: usize = 4;
+
+/// Condition on a single quran character surrounding the one being transliterated.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Condition {
+    /// Exactly this character.
+    Char(char),
+    /// No character before, i.e. the start of the transliterated text.
+    Start,
+    /// No character after, i.e. the end of the quran text.
+    End,
+    /// A space, a waqf mark, a new line, or no character.
+    WordBoundary,
+    /// A waqf mark, a new line (end of aya), or no character.
+    Pause,
+    /// One of huruf syamsiyah, the letters assimilating the lam of the definite article.
+    SunLetter,
+    /// One of huruf qamariyah, the letters keeping the lam of the definite article.
+    MoonLetter,
+    /// One of the letters causing ikhfa of noon sakinah and tanween.
+    IkhfaLetter,
+    /// A short vowel mark, or a tanween.
+    Harakah,
+}
+
+impl Condition {
+    pub(super) fn matches(self, c: Option<char>) -> bool {
+        match (self, c) {
+            (Self::Char(x), Some(c)) => x == c,
+            (Self::Start | Self::End, c) => c.is_none(),
+            (Self::WordBoundary, Some(c)) => c == SPACE || c == '\n' || is_waqf_mark(c),
+            (Self::Pause, Some(c)) => c == '\n' || is_waqf_mark(c),
+            (Self::WordBoundary | Self::Pause, None) => true,
+            (Self::SunLetter, Some(c)) => is_sun_letter(c),
+            (Self::MoonLetter, Some(c)) => is_moon_letter(c),
+            (Self::IkhfaLetter, Some(c)) => is_ikhfa_letter(c),
+            (Self::Harakah, Some(c)) => is_harakah(c),
+            _ => false,
+        }
+    }
+
+    pub(super) fn char(self) -> Option<char> {
+        match self {
+            Self::Char(c) => Some(c),
+            _ => None,
+        }
+    }
+}
+
+/// State of a transliteration walk: the last [`CONTEXT_SIZE`] quran characters seen,
+/// and the conditions pending on the quran characters coming next.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Context<'a> {
+    before: [Option<char>; CONTEXT_SIZE],
+    after: Vec<&'a [Condition]>,
+}
+
+impl<'a> Context<'a> {
+    pub(super) fn before(&self) -> &[Option<char>; CONTEXT_SIZE] {
+        &self.before
+    }
+
+    pub(crate) fn last(&self) -> Option<char> {
+        self.before[CONTEXT_SIZE - 1]
+    }
+
+    /// Move past `c`, or return `None` when `c` violates a pending condition.
+    pub(crate) fn step(&self, c: char) -> Option<Self> {
+        let mut before = [None; CONTEXT_SIZE];
+        before[..CONTEXT_SIZE - 1].copy_from_slice(&self.before[1..]);
+        before[CONTEXT_SIZE - 1] = Some(c);
+        let after = self.step_after(Some(c))?;
+        Some(Self { before, after })
+    }
+
+    /// Whether the end of the quran text satisfies all pending conditions.
+    pub(crate) fn accepts_end(&self) -> bool {
+        self.after
+            .iter()
+            .all(|conds| conds.iter().all(|c| c.matches(None)))
+    }
+
+    pub(crate) fn is_settled(&self) -> bool {
+        self.after.is_empty()
+    }
+
+    pub(crate) fn expect(mut self, after: &'a [Condition]) -> Self {
+        if !after.is_empty() {
+            self.after.push(after);
+        }
+        self
+    }
+
+    fn step_after(&self, c: Option<char>) -> Option<Vec<&'a [Condition]>> {
+        let mut after = Vec::with_capacity(self.after.len());
+        for conds in &self.after {
+            match conds.split_first() {
+                Some((cond, _)) if !cond.matches(c) => return None,
+                Some((_, [])) | None => {}
+                Some((_, conds)) => after.push(conds),
+            }
+        }
+        Some(after)
+    }
+}
@@ -1,36 +1,43 @@
-mod chars;
+pub(crate) mod chars;
+mod context;
+mod table;
 
 use chars::*;
+use Condition::*;
 
-pub(super) fn map(c: char) -> &'static [&'static str] {
+pub use context::Condition;
+pub(crate) use context::Context;
+pub use table::{ContextualRule, Table, TableError};
+
+fn map(c: char) -> &'static [&'static str] {
     match c {
         SPACE => &[""],
 
-        LETTER_HAMZA => &["", "'", "k"],
-        LETTER_ALEF_WITH_MADDA_ABOVE => &["a", "aa", "'a", "'aa"],
-        LETTER_ALEF_WITH_HAMZA_ABOVE => &["", "'", "k"],
-        LETTER_WAW_WITH_HAMZA_ABOVE => &["", "'", "k"],
-        LETTER_ALEF_WITH_HAMZA_BELOW => &["", "'", "k"],
-        LETTER_YEH_WITH_HAMZA_ABOVE => &["", "'", "k"],
-        LETTER_ALEF => &["a", "aa", "o", "oo", ""],
+        LETTER_HAMZA => &["", "'", "k", "ʾ", "2"],
+        LETTER_ALEF_WITH_MADDA_ABOVE => &["a", "aa", "'a", "'aa", "ā", "ʾā", "2a", "2aa"],
+        LETTER_ALEF_WITH_HAMZA_ABOVE => &["", "'", "k", "ʾ", "2"],
+        LETTER_WAW_WITH_HAMZA_ABOVE => &["", "'", "k", "ʾ", "2"],
+        LETTER_ALEF_WITH_HAMZA_BELOW => &["", "'", "k", "ʾ", "2"],
+        LETTER_YEH_WITH_HAMZA_ABOVE => &["", "'", "k", "ʾ", "2"],
+        LETTER_ALEF => &["a", "aa", "o", "oo", "", "ā"],
         LETTER_BEH => &["b"],
         LETTER_TEH_MARBUTA => &["h", "t"],
         LETTER_TEH => &["t"],
         LETTER_THEH => &["ts", "s"],
         LETTER_JEEM => &["j"],
-        LETTER_HAH => &["h", "kh", "ch"],
-        LETTER_KHAH => &["kh"],
+        LETTER_HAH => &["h", "kh", "ch", "ḥ", "7"],
+        LETTER_KHAH => &["kh", "5"],
         LETTER_DAL => &["d"],
         LETTER_THAL => &["d", "dh", "dz"],
         LETTER_REH => &["r"],
         LETTER_ZAIN => &["z"],
         LETTER_SEEN => &["s"],
         LETTER_SHEEN => &["s", "sy", "sh"],
-        LETTER_SAD => &["s", "sh"],
-        LETTER_DAD => &["d", "dh", "dz"],
-        LETTER_TAH => &["t", "th"],
-        LETTER_ZAH => &["d", "dh", "dz"],
-        LETTER_AIN => &["", "'", "k"],
+        LETTER_SAD => &["s", "sh", "ṣ", "9"],
+        LETTER_DAD => &["d", "dh", "dz", "ḍ"],
+        LETTER_TAH => &["t", "th", "ṭ", "6"],
+        LETTER_ZAH => &["d", "dh", "dz", "ẓ"],
+        LETTER_AIN => &["", "'", "k", "ʿ", "3"],
         LETTER_GHAIN => &["g", "gh"],
 
         TATWEEL => &[""],
@@ -42,9 +49,9 @@ pub(super) fn map(c: char) -> &'static [&'static str] {
         LETTER_MEEM => &["m"],
         LETTER_NOON => &["n"],
         LETTER_HEH => &["h"],
-        LETTER_WAW => &["w", "u", "uu"],
-        LETTER_ALEF_MAKSURA => &["a", "aa", "y", "i", "ii"],
-        LETTER_YEH => &["y", "i", "ii"],
+        LETTER_WAW => &["w", "u", "uu", "ū"],
+        LETTER_ALEF_MAKSURA => &["a", "aa", "y", "i", "ii", "ā", "ī"],
+        LETTER_YEH => &["y", "i", "ii", "ī"],
 
         FATHATAN => &["an", "on", ""],
         DAMMATAN => &["un"],
@@ -54,28 +61,110 @@ pub(super) fn map(c: char) -> &'static [&'static str] {
         KASRA => &["i"],
         SHADDA => &[],
 
-        HAMZA_ABOVE => &["'", "a"],
-        LETTER_SUPERSCRIPT_ALEF => &["a", "aa", "o", "oo"],
+        HAMZA_ABOVE => &["'", "a", "ʾ", "2"],
+        LETTER_SUPERSCRIPT_ALEF => &["a", "aa", "o", "oo", "ā"],
 
         _ => &[""],
     }
 }
 
-pub(super) fn contextual_map(context: Option<char>, c: char) -> &'static [&'static str] {
-    match (context, c) {
-        (None, LETTER_ALEF) => &["u", "i"],
-        (Some(DAMMA), LETTER_WAW) => &[""],
-        (Some(EMPTY_CENTRE_LOW_STOP), LETTER_ALEF_MAKSURA) => &[""],
-        (Some(FATHATAN), LETTER_ALEF_MAKSURA) => &[""],
-        (Some(KASRA), LETTER_LAM) => &[""],
-        (Some(LETTER_ALEF_MAKSURA), LETTER_SUPERSCRIPT_ALEF) => &[""],
-        (Some(LETTER_ALEF), LETTER_LAM) => &[""],
-        (Some(LETTER_JEEM), LETTER_REH) => &["re", "ree"],
+type ContextualMapping = (
+    &'static [Condition],
+    char,
+    &'static [Condition],
+    &'static [&'static str],
+);
 
-        (Some(c), SHADDA) => map(c),
+#[rustfmt::skip]
+const CONTEXTUAL_MAP: [ContextualMapping; 10] = [
+    (&[Start], LETTER_ALEF, &[], &["u", "i"]),
+    (&[Char(DAMMA)], LETTER_WAW, &[], &[""]),
+    (&[Char(EMPTY_CENTRE_LOW_STOP)], LETTER_ALEF_MAKSURA, &[], &[""]),
+    (&[Char(FATHATAN)], LETTER_ALEF_MAKSURA, &[], &[""]),
+    (&[Char(KASRA)], LETTER_LAM, &[], &[""]),
+    (&[Char(LETTER_ALEF_MAKSURA)], LETTER_SUPERSCRIPT_ALEF, &[], &[""]),
+    (&[Start, Char(LETTER_ALEF)], LETTER_LAM, &[], &[""]),
+    (&[Char(LETTER_ALEF)], LETTER_LAM, &[SunLetter], &[""]),
+    (&[Char(LETTER_JEEM)], LETTER_REH, &[Char(LETTER_ALEF)], &["re", "ree"]),
+    (&[Char(LETTER_LAM), Char(SHADDA)], FATHA, &[Char(LETTER_HEH)], &["ā"]),
+];
 
-        _ => &[],
+type Nunation = (char, &'static [&'static str], &'static [Option<char>]);
+
+const NUNATIONS: [Nunation; 4] = [
+    (LETTER_NOON, &[""], &[None]),
+    (
+        FATHATAN,
+        &["a", "o"],
+        &[None, Some(LETTER_ALEF), Some(LETTER_ALEF_MAKSURA)],
+    ),
+    (DAMMATAN, &["u"], &[None]),
+    (KASRATAN, &["i"], &[None]),
+];
+
+const IDGHAM_LETTERS: [(char, &str); 6] = [
+    (LETTER_YEH, "y"),
+    (LETTER_NOON, "n"),
+    (LETTER_MEEM, "m"),
+    (LETTER_WAW, "w"),
+    (LETTER_LAM, "l"),
+    (LETTER_REH, "r"),
+];
+
+/// Alternatives of what is heard when applying tajweed rules:
+/// - iqlab, ikhfa and idgham of noon sakinah and tanween,
+/// - idgham mitslain of meem sakinah,
+/// - qalqalah of sukun letters at the end of a word or an aya.
+fn tajweed_map() -> Vec<ContextualRule> {
+    let mut rules = vec![];
+    for (letter, vowels, seats) in NUNATIONS {
+        let mut push = |after: Vec<Condition>, sounds: &[&str]| {
+            let alternatives: Vec<String> = { vowels.iter() }
+                .flat_map(|v| sounds.iter().map(move |s| format!("{}{}", v, s)))
+                .filter(|tsl| !map(letter).contains(&tsl.as_str()))
+                .collect();
+            if alternatives.is_empty() {
+                return;
+            }
+            rules.push(ContextualRule {
+                before: vec![],
+                letter,
+                after,
+                alternatives,
+            });
+        };
+        for seat in seats {
+            let across_words =
+                |c| Vec::from_iter(seat.map(Char).into_iter().chain([Char(SPACE), c]));
+            push(across_words(Char(LETTER_BEH)), &["m"]);
+            push(across_words(IkhfaLetter), &["ng"]);
+            { IDGHAM_LETTERS.into_iter() }
+                .for_each(|(c, sound)| push(across_words(Char(c)), &[sound]));
+        }
+        if letter == LETTER_NOON {
+            push(vec![Char(LETTER_BEH)], &["m"]);
+            push(vec![IkhfaLetter], &["ng"]);
+        }
+    }
+    rules.push(ContextualRule {
+        before: vec![],
+        letter: LETTER_MEEM,
+        after: vec![Char(SPACE), Char(LETTER_MEEM)],
+        alternatives: vec![String::new()],
+    });
+    for letter in KNOWN_CHARS.into_iter().filter(|&c| is_qalqalah_letter(c)) {
+        let alternatives = Vec::from_iter(map(letter).iter().map(|tsl| format!("{}e", tsl)));
+        for after in [vec![WordBoundary], vec![Harakah, Pause]] {
+            let alternatives = alternatives.clone();
+            rules.push(ContextualRule {
+                before: vec![],
+                letter,
+                after,
+                alternatives,
+            });
+        }
     }
+    rules
 }
 
 pub(super) fn harf_muqottoah_map(c: char) -> &'static [&'static str] {
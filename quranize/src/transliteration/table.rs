@@ -0,0 +1,286 @@
+use std::{collections::BTreeMap, fmt};
+
+use super::chars::{KNOWN_CHARS, SHADDA};
+use super::context::{Condition, Context, CONTEXT_SIZE};
+
+static DEFAULT_ALTERNATIVES: [String; 1] = [String::new()];
+
+/// Transliteration table, mapping quran characters into their alphabetic alternatives.
+///
+/// The default table is the one [`crate::Quranize::new`] uses.
+/// With feature `serde` enabled, a table can be (de)serialized, e.g. from a JSON or TOML file,
+/// and then loaded with [`crate::Quranize::with_table`].
+///
+/// Characters missing from `map` are transliterated as an empty string.
+/// A [`SHADDA`] always doubles the alternatives of the character before it,
+/// on top of the rules in `contextual_map`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Table {
+    /// Alphabetic alternatives of each quran character, in any context.
+    pub map: BTreeMap<char, Vec<String>>,
+    /// Additional alternatives of quran characters in specific contexts.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub contextual_map: Vec<ContextualRule>,
+    /// Alternatives of the letters of harf muqottoah, e.g. `alif` for ا, spelled by their names.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub harf_muqottoah_map: BTreeMap<char, Vec<String>>,
+}
+
+/// Additional alternatives for `letter`, applied when the quran characters around it
+/// satisfy the conditions in `before` and `after`.
+///
+/// `before` is matched against the characters right before `letter`, the last condition against
+/// the nearest character, and can be at most 4 conditions long.
+/// `after` is matched against the characters right after `letter`, the first condition against
+/// the nearest character.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ContextualRule {
+    /// Conditions on the quran characters before `letter`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub before: Vec<Condition>,
+    /// Quran character the rule applies to.
+    pub letter: char,
+    /// Conditions on the quran characters after `letter`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub after: Vec<Condition>,
+    /// Alternatives added for `letter`.
+    pub alternatives: Vec<String>,
+}
+
+impl ContextualRule {
+    fn matches_before(&self, context: &Context) -> bool {
+        let before = context.before();
+        self.before.len() <= before.len() && { self.before.iter().rev() }
+            .zip(before.iter().rev())
+            .all(|(cond, &c)| cond.matches(c))
+    }
+}
+
+/// Error returned when a [`Table`] is not usable for transliteration.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TableError {
+    /// The table refers to a character not occurring in the quran text.
+    UnknownChar(char),
+    /// A [`ContextualRule::before`] has more conditions than the number of characters kept in context.
+    ContextTooWide(usize),
+}
+
+impl fmt::Display for TableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownChar(c) => {
+                write!(f, "unknown quran character {:?} (U+{:04X})", c, *c as u32)
+            }
+            Self::ContextTooWide(n) => {
+                write!(
+                    f,
+                    "{} conditions before a letter, at most {} allowed",
+                    n, CONTEXT_SIZE
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for TableError {}
+
+impl Table {
+    /// Check that every character in this table is a known quran character,
+    /// and that every contextual rule fits in the context.
+    pub fn validate(&self) -> Result<(), TableError> {
+        if let Some(r) = { self.contextual_map.iter() }.find(|r| r.before.len() > CONTEXT_SIZE) {
+            return Err(TableError::ContextTooWide(r.before.len()));
+        }
+        let rule_chars = self.contextual_map.iter().flat_map(|r| {
+            let conds = r.before.iter().chain(&r.after);
+            conds.filter_map(|cond| cond.char()).chain([r.letter])
+        });
+        { self.map.keys().copied() }
+            .chain(rule_chars)
+            .chain(self.harf_muqottoah_map.keys().copied())
+            .find(|c| !KNOWN_CHARS.contains(c))
+            .map_or(Ok(()), |c| Err(TableError::UnknownChar(c)))
+    }
+
+    pub(crate) fn map(&self, c: char) -> &[String] {
+        self.map
+            .get(&c)
+            .map_or(&DEFAULT_ALTERNATIVES, Vec::as_slice)
+    }
+
+    /// Alternatives of `c` in `context`, each paired with the conditions it puts on
+    /// the quran characters after `c`.
+    /// An alternative is left out when the same transliteration is already allowed
+    /// with a prefix of its conditions, so that no reading is walked twice.
+    pub(crate) fn alternatives<'a: 'b, 'b>(
+        &'a self,
+        context: &'b Context,
+        c: char,
+    ) -> impl Iterator<Item = (&'a str, &'a [Condition])> + 'b {
+        let map = self.map(c).iter().map(|tsl| (tsl.as_str(), &[][..]));
+        let rules = { self.contextual_map.iter() }
+            .filter(move |r| r.letter == c && r.matches_before(context))
+            .flat_map(|r| {
+                r.alternatives
+                    .iter()
+                    .map(|tsl| (tsl.as_str(), &r.after[..]))
+            });
+        let shadda = match (context.last(), c) {
+            (Some(last), SHADDA) => self.map(last),
+            _ => &[],
+        };
+        let mut alternatives: Vec<(&str, &[Condition])> = vec![];
+        for (tsl, after) in map
+            .chain(rules)
+            .chain(shadda.iter().map(|tsl| (tsl.as_str(), &[][..])))
+        {
+            let covers = |(t, a): (&str, &[Condition]), (u, b): (&str, &[Condition])| {
+                t == u && b.starts_with(a)
+            };
+            if !alternatives.iter().any(|&x| covers(x, (tsl, after))) {
+                alternatives.retain(|&x| !covers((tsl, after), x));
+                alternatives.push((tsl, after));
+            }
+        }
+        alternatives.into_iter()
+    }
+
+    pub(crate) fn harf_muqottoah_map(&self, c: char) -> &[String] {
+        self.harf_muqottoah_map.get(&c).map_or(&[], Vec::as_slice)
+    }
+}
+
+impl Default for Table {
+    fn default() -> Self {
+        let to_strings = |tsls: &[&str]| Vec::from_iter(tsls.iter().map(|tsl| tsl.to_string()));
+        let map = { KNOWN_CHARS.into_iter() }
+            .map(|c| (c, to_strings(super::map(c))))
+            .collect();
+        let contextual_map = { super::CONTEXTUAL_MAP.iter() }
+            .map(|&(before, letter, after, tsls)| ContextualRule {
+                before: before.to_vec(),
+                letter,
+                after: after.to_vec(),
+                alternatives: to_strings(tsls),
+            })
+            .chain(super::tajweed_map())
+            .collect();
+        let harf_muqottoah_map = { KNOWN_CHARS.into_iter() }
+            .map(|c| (c, to_strings(super::harf_muqottoah_map(c))))
+            .filter(|(_, tsls)| !tsls.is_empty())
+            .collect();
+        Self {
+            map,
+            contextual_map,
+            harf_muqottoah_map,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transliteration::chars::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_default_table() {
+        let table = Table::default();
+        assert_eq!(table.validate(), Ok(()));
+        assert_eq!(table.map(LETTER_BEH), ["b"]);
+        assert_eq!(table.map(SHADDA), [] as [String; 0]);
+        assert_eq!(table.map('\u{06D6}'), [""]);
+        let mut with_marks = table.clone();
+        with_marks.map.insert('\u{06DC}', vec!["".to_string()]);
+        with_marks.map.insert('\u{06E9}', vec!["".to_string()]);
+        assert_eq!(with_marks.validate(), Ok(()));
+        assert_eq!(table.harf_muqottoah_map(LETTER_NOON), ["nun"]);
+        assert_eq!(table.harf_muqottoah_map(LETTER_BEH), [] as [String; 0]);
+        assert!({ table.contextual_map.iter() }.all(|r| r
+            .alternatives
+            .iter()
+            .all(|t| !table.map(r.letter).contains(t))));
+        let tsls = |cs, c| Vec::from_iter(alternatives(&table, cs, c).into_iter().map(|(t, _)| t));
+        assert_eq!(
+            tsls(&[], LETTER_ALEF),
+            ["a", "aa", "o", "oo", "", "ā", "u", "i"]
+        );
+        assert_eq!(tsls(&[SPACE], LETTER_ALEF), ["a", "aa", "o", "oo", "", "ā"]);
+        assert_eq!(tsls(&[LETTER_JEEM], LETTER_REH), ["r", "re", "ree"]);
+        assert_eq!(tsls(&[LETTER_SHEEN], SHADDA), ["s", "sy", "sh"]);
+        assert_eq!(tsls(&[LETTER_BEH], LETTER_REH), ["r"]);
+        assert_eq!(tsls(&[LETTER_ALEF], LETTER_LAM), ["l", ""]);
+        assert_eq!(
+            alternatives(&table, &[LETTER_ALEF], LETTER_LAM),
+            [("l", vec![]), ("", vec![])],
+        );
+        assert_eq!(tsls(&[FATHA, LETTER_ALEF], LETTER_LAM), ["l", ""]);
+        assert_eq!(
+            alternatives(&table, &[FATHA, LETTER_ALEF], LETTER_LAM),
+            [("l", vec![]), ("", vec![Condition::SunLetter])],
+        );
+        assert_eq!(
+            alternatives(&table, &[LETTER_JEEM], LETTER_REH)[1],
+            ("re", vec![Condition::Char(LETTER_ALEF)]),
+        );
+    }
+
+    fn alternatives<'a>(table: &'a Table, cs: &[char], c: char) -> Vec<(&'a str, Vec<Condition>)> {
+        let ctx = cs
+            .iter()
+            .fold(Context::default(), |ctx, &c| ctx.step(c).unwrap());
+        let alternatives = table.alternatives(&ctx, c);
+        alternatives
+            .map(|(tsl, after)| (tsl, after.to_vec()))
+            .collect()
+    }
+
+    #[test]
+    fn test_validate() {
+        let mut table = Table::default();
+        table.contextual_map.push(ContextualRule {
+            before: vec![Condition::Char('b')],
+            letter: LETTER_BEH,
+            after: vec![],
+            alternatives: vec!["bb".to_string()],
+        });
+        assert_eq!(table.validate(), Err(TableError::UnknownChar('b')));
+        table.contextual_map.last_mut().unwrap().before = vec![Condition::WordBoundary; 5];
+        assert_eq!(table.validate(), Err(TableError::ContextTooWide(5)));
+        assert_eq!(
+            TableError::UnknownChar('b').to_string(),
+            "unknown quran character 'b' (U+0062)",
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let table = Table::default();
+        let json = serde_json::to_string(&table).unwrap();
+        assert_eq!(serde_json::from_str::<Table>(&json).unwrap(), table);
+
+        let json = r#"{"map": {"ب": ["b", "bh"]}, "harf_muqottoah_map": {"ن": ["noon"]}}"#;
+        let table: Table = serde_json::from_str(json).unwrap();
+        assert_eq!(table.map(LETTER_BEH), ["b", "bh"]);
+        assert_eq!(table.map(LETTER_TEH), [""]);
+        assert_eq!(table.harf_muqottoah_map(LETTER_NOON), ["noon"]);
+        assert!(table.contextual_map.is_empty());
+
+        let json = r#"{"map": {}, "contextual_map": [
+            {"before": ["start", {"char": "ا"}], "letter": "ل", "after": ["sun_letter"], "alternatives": [""]}
+        ]}"#;
+        let table: Table = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            table.contextual_map,
+            [ContextualRule {
+                before: vec![Condition::Start, Condition::Char(LETTER_ALEF)],
+                letter: LETTER_LAM,
+                after: vec![Condition::SunLetter],
+                alternatives: vec!["".to_string()],
+            }],
+        );
+    }
+}
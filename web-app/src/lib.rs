@@ -60,25 +60,24 @@ impl JsQuranize {
         to_value(&self.get_locations(quran))
     }
 
-    fn get_locations(&self, quran: &str) -> Vec<JsLocation> {
-        { self.quranize.find(quran).into_iter() }
-            .map(|(i, j)| {
-                let sura_number = self.quranize.get_sura(i).unwrap_or_default();
-                let aya_number = self.quranize.get_aya(i).unwrap_or_default();
-                let aya = self.quranize.get_quran(i).unwrap_or_default();
-                let offset = aya
-                    .get(j + quran.len()..)
-                    .and_then(|s| Some(s.split(' ').next()?.len()))
-                    .unwrap_or_default();
-                let k = j + quran.len() + offset;
-                JsLocation {
-                    index: i,
-                    sura_number,
-                    aya_number,
-                    before_text: aya.get(..j).unwrap_or_default(),
-                    text: aya.get(j..k).unwrap_or_default(),
-                    after_text: aya.get(k..).unwrap_or_default(),
-                }
+    fn get_locations<'a>(&'a self, quran: &str) -> Vec<JsLocation<'a>> {
+        { self.quranize.find_matches(quran).into_iter() }
+            .filter_map(|m| {
+                let aya = self.quranize.get_quran(m.row).unwrap_or_default();
+                let word_count = { self.quranize.get_match(m.row, 0, aya.len()) }
+                    .map_or(m.word_end, |aya| aya.word_end);
+                let context = self
+                    .quranize
+                    .context(&m, m.word_start, word_count - m.word_end)?;
+                let within_aya = |parts: &[(usize, &'a str)]| parts.first().map_or("", |&(_, s)| s);
+                Some(JsLocation {
+                    index: m.row,
+                    sura_number: self.quranize.get_sura(m.row).unwrap_or_default(),
+                    aya_number: self.quranize.get_aya(m.row).unwrap_or_default(),
+                    before_text: within_aya(&context.before),
+                    text: context.text,
+                    after_text: within_aya(&context.after),
+                })
             })
             .collect()
     }
@@ -150,6 +149,17 @@ mod tests {
         assert_eq!("بِسمِ اللَّهِ ", l.before_text);
         assert_eq!("الرَّحمـٰنِ الرَّحيمِ", l.text);
         assert_eq!("", l.after_text);
+
+        let locs = q.get_locations("عَنّي");
+        let l = locs
+            .iter()
+            .find(|l| (l.sura_number, l.aya_number) == (69, 28))
+            .unwrap();
+        assert_eq!(" مالِيَه ۜ", l.after_text);
+        assert_eq!(
+            format!("{}{}{}", l.before_text, l.text, l.after_text),
+            q.quranize.get_quran(l.index).unwrap()
+        );
     }
 
     #[test]